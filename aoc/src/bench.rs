@@ -0,0 +1,147 @@
+use aoc::days;
+use common::Part;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Where the committed baseline lives, relative to the workspace root
+pub const BASELINE: &str = "bench.json";
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Stats {
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub p95_ns: u64,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        let mut sorted: Vec<u64> = samples.iter().map(|d| d.as_nanos() as u64).collect();
+        sorted.sort();
+        let rank = |p: f64| sorted[((p * sorted.len() as f64).ceil() as usize).max(1) - 1];
+        Self {
+            min_ns: sorted[0],
+            median_ns: rank(0.5),
+            p95_ns: rank(0.95),
+        }
+    }
+
+    /// How much slower this is than `baseline`, as a fraction of the baseline median
+    pub fn change(&self, baseline: &Stats) -> f64 {
+        (self.median_ns as f64 - baseline.median_ns as f64) / baseline.median_ns.max(1) as f64
+    }
+}
+
+fn ms(ns: u64) -> f64 {
+    ns as f64 / 1_000_000.0
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "min {:.3}ms median {:.3}ms p95 {:.3}ms",
+            ms(self.min_ns),
+            ms(self.median_ns),
+            ms(self.p95_ns)
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Measurement {
+    pub parse: Stats,
+    pub solve: Stats,
+}
+
+/// Benchmarks keyed on `dayNN-partN`
+pub type Baseline = BTreeMap<String, Measurement>;
+
+pub fn load(path: &Path) -> io::Result<Baseline> {
+    match fs::read_to_string(path) {
+        Ok(contents) => serde_json::from_str(&contents).map_err(io::Error::other),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Baseline::new()),
+        Err(e) => Err(e),
+    }
+}
+
+pub fn save(path: &Path, baseline: &Baseline) -> io::Result<()> {
+    let mut contents = serde_json::to_string_pretty(baseline).map_err(io::Error::other)?;
+    contents.push('\n');
+    fs::write(path, contents)
+}
+
+pub struct Bench {
+    pub warmup: usize,
+    pub samples: usize,
+}
+
+impl Bench {
+    /// Time parsing and solving a part in-process, discarding the warmup runs
+    pub fn measure(&self, day: &str, part: Part, input: &str) -> io::Result<Option<Measurement>> {
+        let Some(solver) = days::solver(day) else {
+            return Ok(None);
+        };
+        let mut parse = Vec::with_capacity(self.samples);
+        let mut solve = Vec::with_capacity(self.samples);
+        for i in 0..(self.warmup + self.samples) {
+            let start = Instant::now();
+            let invalid =
+                |e| io::Error::new(io::ErrorKind::InvalidData, format!("{}/input {}", day, e));
+            let parsed = solver.parse_any(input).map_err(invalid)?;
+            let parsed_at = Instant::now();
+            let answer = solver.solve_any(parsed.as_ref(), part).map_err(invalid)?;
+            let solved_at = Instant::now();
+            std::hint::black_box(answer);
+            if i >= self.warmup {
+                parse.push(parsed_at - start);
+                solve.push(solved_at - parsed_at);
+            }
+        }
+        Ok(Some(Measurement {
+            parse: Stats::from_samples(&parse),
+            solve: Stats::from_samples(&solve),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn convert_samples(samples: &[u64]) -> Vec<Duration> {
+        samples.iter().map(|ns| Duration::from_nanos(*ns)).collect()
+    }
+
+    #[test]
+    fn stats() {
+        let samples: Vec<u64> = (1..=20).rev().collect();
+        let stats = Stats::from_samples(&convert_samples(&samples));
+        assert_eq!(
+            stats,
+            Stats {
+                min_ns: 1,
+                median_ns: 10,
+                p95_ns: 19,
+            }
+        );
+        let single = Stats::from_samples(&convert_samples(&[7]));
+        assert_eq!(single.median_ns, 7);
+        assert_eq!(single.p95_ns, 7);
+    }
+
+    #[test]
+    fn change() {
+        let stats = |median_ns| Stats {
+            min_ns: 0,
+            median_ns,
+            p95_ns: 0,
+        };
+        assert_eq!(stats(110).change(&stats(100)), 0.1);
+        assert_eq!(stats(50).change(&stats(100)), -0.5);
+    }
+}
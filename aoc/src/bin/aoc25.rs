@@ -0,0 +1,130 @@
+use aoc::days;
+use aoc::workspace::Workspace;
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser};
+use clio::Input;
+use common::output::{self, Format};
+use common::{Part, Solver};
+use std::fs;
+use std::io::{self, prelude::*};
+use std::process::ExitCode;
+
+/// Every day's solutions in one binary
+#[derive(Parser)]
+#[clap(name = "aoc25")]
+struct Opt {
+    /// The day to solve (dayNN), or `all` for every day against its dayNN/input in the workspace
+    target: String,
+
+    /// Which part to solve (part1, part2), both if unspecified, or the input if it isn't a part
+    part: Option<String>,
+
+    /// Input file, use '-' for stdin
+    #[clap(value_parser, default_value = "-")]
+    input: Input,
+
+    /// Which part to solve, as an alternative to giving it before the input
+    #[clap(short = 'p', long = "part", value_name = "PART")]
+    part_flag: Option<Part>,
+
+    /// how to print the answers
+    #[clap(long, value_enum, default_value_t)]
+    format: Format,
+
+    /// the number of CPU cores to use (all if unspecified)
+    #[clap(short, long, default_value_t = num_cpus::get())]
+    threads: usize,
+}
+
+/// Print each part's answer, labelled unless it's the only thing being printed
+fn solve(solver: &dyn Solver, day: &str, parts: &[Part], input: &str, opt: &Opt) -> bool {
+    let label = parts.len() > 1 || opt.target == "all";
+    for &part in parts {
+        match output::solve(solver, day, part, input) {
+            Ok(report) => match opt.format {
+                Format::Text if label => println!("{} {}: {}", day, part, report.answer),
+                Format::Text => println!("{}", report.answer),
+                Format::Json => println!("{}", report.json()),
+            },
+            Err(e) => {
+                eprint!("{}: {}", day, e.diagnostic(input));
+                return false;
+            }
+        }
+    }
+    true
+}
+
+/// Sort out which part was asked for, taking a positional that isn't a part, or any with
+/// `--part`, as the input when no input was given after it
+fn resolve_part(opt: &mut Opt) -> Option<Part> {
+    let Some(given) = opt.part.take() else {
+        return opt.part_flag;
+    };
+    match given.parse() {
+        Ok(_) if opt.part_flag.is_some() => Opt::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "give the part either before the input or with --part",
+            )
+            .exit(),
+        Ok(part) => Some(part),
+        Err(_) if opt.input.is_std() => {
+            opt.input = Input::new(&given).unwrap_or_else(|e| {
+                Opt::command()
+                    .error(ErrorKind::InvalidValue, format!("'{}': {}", given, e))
+                    .exit()
+            });
+            opt.part_flag
+        }
+        Err(e) => Opt::command().error(ErrorKind::InvalidValue, e).exit(),
+    }
+}
+
+fn run(opt: &mut Opt) -> io::Result<bool> {
+    let parts = resolve_part(opt).map_or(Part::ALL.to_vec(), |p| vec![p]);
+    let mut ok = true;
+    if opt.target == "all" {
+        let workspace = Workspace::discover(&std::env::current_dir()?)?;
+        for day in days::DAYS {
+            let input = match fs::read_to_string(workspace.day_dir(day).join("input")) {
+                Ok(input) => input,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e),
+            };
+            let solver = days::solver(day).unwrap();
+            ok &= solve(solver.as_ref(), day, &parts, &input, opt);
+        }
+    } else {
+        let Some(solver) = days::solver(&opt.target) else {
+            eprintln!(
+                "error: '{}' is neither `all` nor a day ({})",
+                opt.target,
+                days::DAYS.join(", ")
+            );
+            return Ok(false);
+        };
+        let mut input = String::new();
+        opt.input.read_to_string(&mut input)?;
+        ok = solve(solver.as_ref(), &opt.target, &parts, &input, opt);
+    }
+    Ok(ok)
+}
+
+fn main() -> ExitCode {
+    let mut opt = Opt::parse();
+
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(opt.threads)
+        .build_global()
+        .unwrap();
+
+    match run(&mut opt) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use common::Solver;
+
+macro_rules! days {
+    ($($day:ident => $solution:ty),* $(,)?) => {
+        /// Every registered day, in order
+        pub const DAYS: &[&str] = &[$(stringify!($day)),*];
+
+        /// The day's solution with the configuration the puzzle asks for
+        pub fn solver(day: &str) -> Option<Box<dyn Solver>> {
+            match day {
+                $(stringify!($day) => Some(Box::new(<$solution>::default())),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+// `aoc scaffold` keeps this list sorted as it adds days
+days! {
+    day01 => day01::Day01,
+    day02 => day02::Day02,
+    day03 => day03::Day03,
+    day04 => day04::Day04,
+    day05 => day05::Day05,
+    day06 => day06::Day06,
+    day07 => day07::Day07,
+    day08 => day08::Day08,
+    day09 => day09::Day09,
+    day10 => day10::Day10,
+    day11 => day11::Day11,
+}
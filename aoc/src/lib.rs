@@ -0,0 +1,4 @@
+//! The pieces shared between the `aoc` workspace tool and the `aoc25` multi-call binary
+
+pub mod days;
+pub mod workspace;
@@ -0,0 +1,193 @@
+mod bench;
+mod run;
+mod scaffold;
+
+use aoc::workspace::Workspace;
+use bench::Bench;
+use clap::builder::RangedU64ValueParser;
+use clap::{Parser, Subcommand};
+use common::Part;
+use run::{Runner, Selection, Status};
+use scaffold::Template;
+use std::fs;
+use std::io;
+use std::process::ExitCode;
+use std::time::Duration;
+
+#[derive(Parser)]
+struct Opt {
+    #[clap(subcommand)]
+    command: Cmd,
+}
+
+#[derive(Subcommand)]
+enum Cmd {
+    /// Build, run and check the answers for the selected days and parts
+    Run {
+        /// Days (dayNN) and parts (part1, part2) to run, all of them if unspecified
+        targets: Vec<String>,
+
+        /// Show build output and solver stderr
+        #[clap(short, long)]
+        verbose: bool,
+
+        /// Report the total time taken across every part run
+        #[clap(short, long)]
+        cumulative: bool,
+
+        /// Extra arguments passed through to `cargo build`
+        #[clap(last = true)]
+        cargo_args: Vec<String>,
+    },
+    /// Create a new day from the dayX template and add it to the workspace
+    Scaffold {
+        /// The day to create (dayNN)
+        day: String,
+
+        /// The shape of input the new day's parser should start from
+        #[clap(short, long, value_enum, default_value_t)]
+        template: Template,
+    },
+    /// Time each day's parse and solve in-process and compare them against the baseline
+    Bench {
+        /// Days (dayNN) and parts (part1, part2) to benchmark, all of them if unspecified
+        targets: Vec<String>,
+
+        /// Untimed runs to make before sampling
+        #[clap(short, long, default_value_t = 3)]
+        warmup: usize,
+
+        /// Timed runs to take statistics over
+        #[clap(
+            short,
+            long,
+            default_value_t = 25,
+            value_parser = RangedU64ValueParser::<usize>::new().range(1..)
+        )]
+        samples: usize,
+
+        /// Percentage slowdown of the median over the baseline to flag as a regression
+        #[clap(long, default_value_t = 10.0)]
+        threshold: f64,
+
+        /// Record these results in the baseline
+        #[clap(long)]
+        save: bool,
+    },
+}
+
+fn run(
+    targets: &[String],
+    verbose: bool,
+    cumulative: bool,
+    cargo_args: &[String],
+) -> io::Result<bool> {
+    let workspace = Workspace::discover(&std::env::current_dir()?)?;
+    let selection = Selection::parse(targets, &workspace.days).map_err(io::Error::other)?;
+    let runner = Runner {
+        workspace: &workspace,
+        verbose,
+        cargo_args,
+    };
+
+    let mut elapsed = Duration::ZERO;
+    let mut all_correct = true;
+    for day in workspace.days.iter().filter(|d| selection.wants_day(d)) {
+        for part in selection.parts() {
+            for outcome in runner.run(day, part)? {
+                println!("{}", outcome);
+                elapsed += outcome.elapsed;
+                if let Status::Incorrect { .. } = outcome.status {
+                    all_correct = false;
+                }
+            }
+        }
+    }
+    if cumulative {
+        println!("cumulative: {:.2}s", elapsed.as_secs_f64());
+    }
+    Ok(all_correct)
+}
+
+fn bench(targets: &[String], bench: &Bench, threshold: f64, save: bool) -> io::Result<bool> {
+    let workspace = Workspace::discover(&std::env::current_dir()?)?;
+    let selection = Selection::parse(targets, &workspace.days).map_err(io::Error::other)?;
+    if cfg!(debug_assertions) {
+        eprintln!("warning: benchmarking a debug build, these numbers won't match the baseline");
+    }
+    let baseline_path = workspace.root.join(bench::BASELINE);
+    let mut baseline = bench::load(&baseline_path)?;
+
+    let mut no_regressions = true;
+    for day in workspace.days.iter().filter(|d| selection.wants_day(d)) {
+        let input = match fs::read_to_string(workspace.day_dir(day).join("input")) {
+            Ok(input) if !input.is_empty() => input,
+            _ => continue,
+        };
+        for part in selection.parts() {
+            let part: Part = part.parse().map_err(io::Error::other)?;
+            let Some(measurement) = bench.measure(day, part, &input)? else {
+                continue;
+            };
+            let key = format!("{}-{}", day, part);
+            println!("{} {}:", day, part);
+            let previous = baseline.get(&key);
+            for (name, stats, old) in [
+                ("parse", measurement.parse, previous.map(|p| p.parse)),
+                ("solve", measurement.solve, previous.map(|p| p.solve)),
+            ] {
+                print!("\t{}: {}", name, stats);
+                if let Some(old) = old {
+                    let change = stats.change(&old) * 100.0;
+                    print!(" ({:+.1}%", change);
+                    if change > threshold {
+                        print!(", REGRESSION");
+                        no_regressions = false;
+                    }
+                    print!(")");
+                }
+                println!();
+            }
+            println!();
+            if save {
+                baseline.insert(key, measurement);
+            }
+        }
+    }
+    if save {
+        bench::save(&baseline_path, &baseline)?;
+    }
+    Ok(no_regressions)
+}
+
+fn main() -> ExitCode {
+    let opt = Opt::parse();
+
+    let result = match opt.command {
+        Cmd::Run {
+            targets,
+            verbose,
+            cumulative,
+            cargo_args,
+        } => run(&targets, verbose, cumulative, &cargo_args),
+        Cmd::Scaffold { day, template } => std::env::current_dir()
+            .and_then(|dir| Workspace::discover(&dir))
+            .and_then(|workspace| scaffold::scaffold(&workspace, &day, template))
+            .map(|_| true),
+        Cmd::Bench {
+            targets,
+            warmup,
+            samples,
+            threshold,
+            save,
+        } => bench(&targets, &Bench { warmup, samples }, threshold, save),
+    };
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,257 @@
+use aoc::workspace::Workspace;
+use common::Part;
+use common::answers::{self, Answers, Case};
+use std::fmt;
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+pub const PARTS: [&str; 2] = ["part1", "part2"];
+
+/// Which days and parts were asked for on the command line, empty meaning all of them
+#[derive(Debug, Default, PartialEq)]
+pub struct Selection {
+    pub days: Vec<String>,
+    pub parts: Vec<String>,
+}
+
+impl Selection {
+    /// Sort the targets into days and parts, failing on any day that isn't one of `days`
+    pub fn parse(targets: &[String], days: &[String]) -> Result<Self, String> {
+        let mut ret = Self::default();
+        for target in targets {
+            if days.contains(target) {
+                ret.days.push(target.clone());
+            } else if target.starts_with("day") {
+                return Err(format!(
+                    "'{}' isn't a day in this workspace ({})",
+                    target,
+                    days.join(", ")
+                ));
+            } else if PARTS.contains(&target.as_str()) {
+                ret.parts.push(target.clone());
+            } else {
+                return Err(format!(
+                    "'{}' is neither a day (dayNN) nor a part (part1, part2)",
+                    target
+                ));
+            }
+        }
+        Ok(ret)
+    }
+
+    pub fn wants_day(&self, day: &str) -> bool {
+        self.days.is_empty() || self.days.iter().any(|d| d == day)
+    }
+
+    pub fn parts(&self) -> Vec<&str> {
+        if self.parts.is_empty() {
+            PARTS.to_vec()
+        } else {
+            self.parts.iter().map(|p| p.as_str()).collect()
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Status {
+    Correct,
+    Incorrect { expected: String },
+    Unknown,
+}
+
+impl Status {
+    /// Compare an answer against the recorded one, treating `answers::UNKNOWN` as not
+    /// recorded yet
+    pub fn check(answer: &str, expected: Option<&str>) -> Self {
+        match expected.filter(|expected| !answers::is_unknown(expected)) {
+            None => Status::Unknown,
+            Some(expected) if expected == answer => Status::Correct,
+            Some(expected) => Status::Incorrect {
+                expected: expected.to_string(),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Status::Correct => write!(f, "correct"),
+            Status::Incorrect { expected } => write!(f, "incorrect: {}", expected),
+            Status::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Outcome {
+    pub day: String,
+    pub part: String,
+    pub input: String,
+    pub note: Option<String>,
+    pub answer: String,
+    pub status: Status,
+    pub elapsed: Duration,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{} {} ({}):", self.day, self.part, self.input)?;
+        writeln!(
+            f,
+            "\t{} ({}) time: {:.2}ms",
+            self.answer,
+            self.status,
+            self.elapsed.as_secs_f64() * 1000.0
+        )?;
+        if let Some(note) = &self.note {
+            writeln!(f, "\t{}", note)?;
+        }
+        Ok(())
+    }
+}
+
+pub struct Runner<'a> {
+    pub workspace: &'a Workspace,
+    pub verbose: bool,
+    pub cargo_args: &'a [String],
+}
+
+impl Runner<'_> {
+    pub fn bin_name(day: &str, part: &str) -> String {
+        format!("{}-{}", day, part)
+    }
+
+    /// Build a single day's part in release mode, surfacing the compiler output if it fails
+    pub fn build(&self, day: &str, part: &str) -> io::Result<()> {
+        let bin = Self::bin_name(day, part);
+        let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+        let mut cmd = Command::new(cargo);
+        cmd.current_dir(&self.workspace.root)
+            .args(["build", "-r", "--bin", &bin])
+            .args(self.cargo_args);
+        let status = if self.verbose {
+            cmd.status()?
+        } else {
+            let output = cmd.output()?;
+            if !output.status.success() {
+                io::stderr().write_all(&output.stderr)?;
+            }
+            output.status
+        };
+        if !status.success() {
+            return Err(io::Error::other(format!("failed to build {}", bin)));
+        }
+        Ok(())
+    }
+
+    /// Run a built part against every input in the day's manifest that's on disk, or just
+    /// `input` without one
+    pub fn run(&self, day: &str, part: &str) -> io::Result<Vec<Outcome>> {
+        let dir = self.workspace.day_dir(day);
+        let bin = Self::bin_name(day, part);
+        if !dir.join("src/bin").join(format!("{}.rs", bin)).is_file() {
+            return Ok(Vec::new());
+        }
+        let which: Part = part.parse().map_err(io::Error::other)?;
+        let mut answers = Answers::load(&dir)?;
+        if answers.cases.is_empty() {
+            answers.cases.push(Case {
+                file: "input".to_string(),
+                part1: None,
+                part2: None,
+                note: None,
+            });
+        }
+        // like `answers::verify`, skip inputs that aren't on disk, as not everyone's are
+        answers.cases.retain(|case| {
+            let present = dir.join(&case.file).is_file();
+            if !present && self.verbose {
+                eprintln!(
+                    "skipping {} {} ({}), it isn't on disk",
+                    day, part, case.file
+                );
+            }
+            present && case.covers(which)
+        });
+        if answers.cases.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.build(day, part)?;
+
+        let exe = self.workspace.target_dir().join("release").join(&bin);
+        let mut ret = Vec::new();
+        for case in answers.cases {
+            let start = Instant::now();
+            let output = Command::new(&exe)
+                .arg(dir.join(&case.file))
+                .stderr(if self.verbose {
+                    Stdio::inherit()
+                } else {
+                    Stdio::piped()
+                })
+                .output()?;
+            let elapsed = start.elapsed();
+            if !output.status.success() {
+                io::stderr().write_all(&output.stderr)?;
+                return Err(io::Error::other(format!(
+                    "{} exited with {} on {}",
+                    bin, output.status, case.file
+                )));
+            }
+
+            let answer = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            let status = Status::check(&answer, case.expected(which));
+            ret.push(Outcome {
+                day: day.to_string(),
+                part: part.to_string(),
+                note: case.note.filter(|_| self.verbose),
+                input: case.file,
+                answer,
+                status,
+                elapsed,
+            });
+        }
+        Ok(ret)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn convert_targets(targets: &[&str]) -> Vec<String> {
+        targets.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn selection() {
+        let days = convert_targets(&["day03", "day04", "day11"]);
+        let selection = Selection::parse(&convert_targets(&["day03", "part2"]), &days).unwrap();
+        assert!(selection.wants_day("day03"));
+        assert!(!selection.wants_day("day04"));
+        assert_eq!(selection.parts(), vec!["part2"]);
+
+        let everything = Selection::parse(&[], &days).unwrap();
+        assert!(everything.wants_day("day11"));
+        assert_eq!(everything.parts(), vec!["part1", "part2"]);
+
+        assert!(Selection::parse(&convert_targets(&["part3"]), &days).is_err());
+        assert!(Selection::parse(&convert_targets(&["day99", "part1"]), &days).is_err());
+    }
+
+    #[test]
+    fn status() {
+        assert_eq!(Status::check("1059", Some("1059")), Status::Correct);
+        assert_eq!(
+            Status::check("1058", Some("1059")),
+            Status::Incorrect {
+                expected: "1059".to_string()
+            }
+        );
+        assert_eq!(Status::check("1059", None), Status::Unknown);
+        assert_eq!(Status::check("1059", Some("unk")), Status::Unknown);
+        assert_eq!(Status::check("1059", Some("")), Status::Unknown);
+    }
+}
@@ -0,0 +1,284 @@
+use aoc::workspace::{TEMPLATE, Workspace};
+use clap::ValueEnum;
+use std::fs;
+use std::io;
+use std::path::Path;
+use toml_edit::{DocumentMut, InlineTable, Value};
+
+/// The shape of puzzle input the new day's parser starts out expecting
+#[derive(Clone, Copy, Debug, Default, PartialEq, ValueEnum)]
+pub enum Template {
+    /// one record per line, as in the dayX template itself
+    #[default]
+    Lines,
+    /// a rectangle of characters
+    Grid,
+    /// `node: edge edge ...` adjacency lists
+    Graph,
+    /// blocks of lines separated by blank lines
+    Sections,
+}
+
+impl Template {
+    /// The lib.rs to drop over the template's, if it isn't the template's own
+    fn lib(&self) -> Option<&'static str> {
+        match self {
+            Template::Lines => None,
+            Template::Grid => Some(include_str!("../templates/grid.rs")),
+            Template::Graph => Some(include_str!("../templates/graph.rs")),
+            Template::Sections => Some(include_str!("../templates/sections.rs")),
+        }
+    }
+}
+
+fn invalid(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message.into())
+}
+
+/// The solution type name for a day, i.e. `Day12` for `day12`
+pub fn struct_name(day: &str) -> String {
+    format!("Day{}", &day[3..])
+}
+
+fn validate(day: &str) -> io::Result<()> {
+    let digits = day.strip_prefix("day").unwrap_or_default();
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return Err(invalid(format!("'{}' is not a day (dayNN)", day)));
+    }
+    Ok(())
+}
+
+/// Swap every reference to the template for the new day
+fn rename(contents: &str, day: &str) -> String {
+    contents
+        .replace(TEMPLATE, day)
+        .replace(&struct_name(TEMPLATE), &struct_name(day))
+}
+
+/// Add a day to the workspace members, keeping them sorted and dropping any duplicates
+pub fn add_member(manifest: &str, day: &str) -> io::Result<String> {
+    let mut doc: DocumentMut = manifest
+        .parse()
+        .map_err(|e| invalid(format!("unable to parse Cargo.toml: {}", e)))?;
+    let members = doc
+        .get_mut("workspace")
+        .and_then(|w| w.get_mut("members"))
+        .and_then(|m| m.as_array_mut())
+        .ok_or_else(|| invalid("Cargo.toml has no [workspace] members"))?;
+
+    let mut seen: Vec<String> = Vec::new();
+    let mut i = 0;
+    while i < members.len() {
+        let member = members.get(i).and_then(|m| m.as_str()).map(String::from);
+        match member {
+            Some(m) if seen.contains(&m) => {
+                members.remove(i);
+            }
+            Some(m) => {
+                seen.push(m);
+                i += 1;
+            }
+            None => i += 1,
+        }
+    }
+    if seen.iter().any(|m| m == day) {
+        return Err(invalid(format!("{} is already a workspace member", day)));
+    }
+
+    // slot the new day in after the last member that sorts before it
+    let position = seen
+        .iter()
+        .rposition(|m| m.as_str() < day)
+        .map(|p| p + 1)
+        .unwrap_or(0);
+    members.insert_formatted(position, Value::from(day).decorated("\n    ", ""));
+    members.set_trailing("\n");
+    members.set_trailing_comma(true);
+    Ok(doc.to_string())
+}
+
+/// Add a day as a path dependency of the aoc crate, so it can be solved in-process
+pub fn add_dependency(manifest: &str, day: &str) -> io::Result<String> {
+    let mut doc: DocumentMut = manifest
+        .parse()
+        .map_err(|e| invalid(format!("unable to parse aoc/Cargo.toml: {}", e)))?;
+    let deps = doc
+        .get_mut("dependencies")
+        .and_then(|d| d.as_table_like_mut())
+        .ok_or_else(|| invalid("aoc/Cargo.toml has no [dependencies]"))?;
+    let mut path = InlineTable::new();
+    path.insert("path", Value::from(format!("../{}", day)));
+    deps.insert(day, toml_edit::value(path));
+    Ok(doc.to_string())
+}
+
+/// Add a day to the `days!` registry, keeping it sorted
+pub fn register(registry: &str, day: &str) -> io::Result<String> {
+    let mut lines: Vec<&str> = registry.lines().collect();
+    let start = lines
+        .iter()
+        .position(|l| *l == "days! {")
+        .ok_or_else(|| invalid("no days! registry found"))?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|l| *l == "}")
+            .ok_or_else(|| invalid("days! registry is never closed"))?;
+    let entry = format!("    {} => {}::{},", day, day, struct_name(day));
+    let entries = &lines[start + 1..end];
+    if entries
+        .iter()
+        .any(|l| l.trim_start().starts_with(&format!("{} ", day)))
+    {
+        return Err(invalid(format!("{} is already registered", day)));
+    }
+    let position = start + 1 + entries.iter().filter(|l| l.trim_start() < day).count();
+    lines.insert(position, &entry);
+    let mut ret = lines.join("\n");
+    ret.push('\n');
+    Ok(ret)
+}
+
+fn copy_dir(from: &Path, to: &Path, day: &str) -> io::Result<()> {
+    fs::create_dir(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().replace(TEMPLATE, day);
+        let dest = to.join(name);
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &dest, day)?;
+        } else if dest.extension().is_some_and(|e| e == "rs" || e == "toml") {
+            fs::write(&dest, rename(&fs::read_to_string(entry.path())?, day))?;
+        } else {
+            fs::copy(entry.path(), &dest)?;
+        }
+    }
+    Ok(())
+}
+
+/// Create a new day from the template and register it with the workspace
+pub fn scaffold(workspace: &Workspace, day: &str, template: Template) -> io::Result<()> {
+    validate(day)?;
+    let dest = workspace.day_dir(day);
+    if dest.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!(
+                "{} already exists, refusing to overwrite it",
+                dest.display()
+            ),
+        ));
+    }
+
+    // work everything out before touching the disk, so a failure leaves nothing half-done
+    let manifest_path = workspace.root.join("Cargo.toml");
+    let manifest = add_member(&fs::read_to_string(&manifest_path)?, day)?;
+    let aoc_manifest_path = workspace.root.join("aoc/Cargo.toml");
+    let aoc_manifest = add_dependency(&fs::read_to_string(&aoc_manifest_path)?, day)?;
+    let registry_path = workspace.root.join("aoc/src/days.rs");
+    let registry = register(&fs::read_to_string(&registry_path)?, day)?;
+
+    copy_dir(&workspace.day_dir(TEMPLATE), &dest, day)?;
+    if let Some(lib) = template.lib() {
+        fs::write(dest.join("src/lib.rs"), rename(lib, day))?;
+    }
+    fs::write(manifest_path, manifest)?;
+    fs::write(aoc_manifest_path, aoc_manifest)?;
+    fs::write(registry_path, registry)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: [&str; 10] = [
+        "[workspace]",
+        "members = [",
+        "    \"aoc\",",
+        "    \"common\",",
+        "    \"day01\",",
+        "    \"day01\",",
+        "    \"day02\",",
+        "]",
+        "resolver = \"3\"",
+        "",
+    ];
+
+    #[test]
+    fn members() {
+        let expected = [
+            "[workspace]",
+            "members = [",
+            "    \"aoc\",",
+            "    \"common\",",
+            "    \"day01\",",
+            "    \"day02\",",
+            "    \"day03\",",
+            "]",
+            "resolver = \"3\"",
+            "",
+        ];
+        println!("{}", MANIFEST.join("\n"));
+        assert_eq!(
+            add_member(&MANIFEST.join("\n"), "day03").unwrap(),
+            expected.join("\n")
+        );
+    }
+
+    #[test]
+    fn existing_member() {
+        assert!(add_member(&MANIFEST.join("\n"), "day02").is_err());
+    }
+
+    #[test]
+    fn registry() {
+        let registry = [
+            "use common::Solver;",
+            "",
+            "days! {",
+            "    day01 => day01::Day01,",
+            "    day03 => day03::Day03,",
+            "}",
+        ];
+        let expected = [
+            "use common::Solver;",
+            "",
+            "days! {",
+            "    day01 => day01::Day01,",
+            "    day02 => day02::Day02,",
+            "    day03 => day03::Day03,",
+            "}",
+            "",
+        ];
+        println!("{}", registry.join("\n"));
+        assert_eq!(
+            register(&registry.join("\n"), "day02").unwrap(),
+            expected.join("\n")
+        );
+        assert!(register(&registry.join("\n"), "day03").is_err());
+    }
+
+    #[test]
+    fn dependency() {
+        let manifest = ["[dependencies]", "day01 = { path = \"../day01\" }", ""];
+        assert_eq!(
+            add_dependency(&manifest.join("\n"), "day02").unwrap(),
+            [
+                "[dependencies]",
+                "day01 = { path = \"../day01\" }",
+                "day02 = { path = \"../day02\" }",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn renames() {
+        assert_eq!(rename("use dayX::DayX;", "day12"), "use day12::Day12;");
+        assert!(validate("day12").is_ok());
+        assert!(validate("day").is_err());
+        assert!(validate("twelve").is_err());
+    }
+}
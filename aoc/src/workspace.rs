@@ -0,0 +1,99 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use toml_edit::DocumentMut;
+
+/// The template directory that every day is scaffolded from, never a puzzle itself
+pub const TEMPLATE: &str = "dayX";
+
+pub struct Workspace {
+    pub root: PathBuf,
+    pub days: Vec<String>,
+}
+
+impl Workspace {
+    /// Walk up from `start` until we find the Cargo.toml holding the `[workspace]` table
+    pub fn discover(start: &Path) -> io::Result<Self> {
+        for dir in start.ancestors() {
+            let manifest = dir.join("Cargo.toml");
+            if !manifest.is_file() {
+                continue;
+            }
+            let contents = fs::read_to_string(&manifest)?;
+            if let Ok(days) = days_from_manifest(&contents) {
+                return Ok(Self {
+                    root: dir.to_path_buf(),
+                    days,
+                });
+            }
+        }
+        Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("no workspace Cargo.toml found above {}", start.display()),
+        ))
+    }
+
+    pub fn day_dir(&self, day: &str) -> PathBuf {
+        self.root.join(day)
+    }
+
+    pub fn target_dir(&self) -> PathBuf {
+        std::env::var_os("CARGO_TARGET_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| self.root.join("target"))
+    }
+}
+
+fn invalid(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
+
+/// Pull the sorted, deduplicated puzzle days out of a workspace manifest's members
+pub fn days_from_manifest(contents: &str) -> io::Result<Vec<String>> {
+    let doc: DocumentMut = contents
+        .parse()
+        .map_err(|e| invalid(format!("unable to parse Cargo.toml: {}", e)))?;
+    let members = doc
+        .get("workspace")
+        .and_then(|w| w.get("members"))
+        .and_then(|m| m.as_array())
+        .ok_or_else(|| invalid("Cargo.toml has no [workspace] members"))?;
+    let mut days: Vec<String> = members
+        .iter()
+        .filter_map(|m| m.as_str())
+        .filter(|m| m.starts_with("day") && *m != TEMPLATE)
+        .map(|m| m.to_string())
+        .collect();
+    days.sort();
+    days.dedup();
+    Ok(days)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn members() {
+        let manifest = [
+            "[workspace]",
+            "members = [",
+            "    \"aoc\",",
+            "    \"day02\",",
+            "    \"day01\",",
+            "    \"day01\",",
+            "    \"dayX\",",
+            "]",
+        ];
+        println!("{}", manifest.join("\n"));
+        assert_eq!(
+            days_from_manifest(&manifest.join("\n")).unwrap(),
+            vec!["day01", "day02"]
+        );
+    }
+
+    #[test]
+    fn not_a_workspace() {
+        assert!(days_from_manifest("[package]\nname = \"day01\"\n").is_err());
+    }
+}
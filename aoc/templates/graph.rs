@@ -0,0 +1,63 @@
+use common::Solution;
+use common::input;
+use common::parse::ParseError;
+use std::collections::HashMap;
+
+#[derive(Default)]
+pub struct DayX;
+
+/// Every node, and the nodes it has edges to
+pub type Graph = HashMap<String, Vec<String>>;
+
+impl Solution for DayX {
+    type Parsed = Graph;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Graph, ParseError> {
+        input::lines(input)
+            .map(|line| {
+                let (node, edges) = line
+                    .text
+                    .split_once(":")
+                    .ok_or_else(|| line.error(line.end(), "':'"))?;
+                Ok((
+                    node.to_string(),
+                    edges.split_whitespace().map(|s| s.to_string()).collect(),
+                ))
+            })
+            .collect()
+    }
+
+    fn part1(&self, graph: &Graph) -> usize {
+        graph.len()
+    }
+
+    fn part2(&self, graph: &Graph) -> usize {
+        graph.values().map(|edges| edges.len()).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: [&str; 3] = ["aaa: bbb ccc", "bbb: ccc", "ccc: out"];
+
+    #[test]
+    fn part1_given() {
+        println!("{}", EXAMPLE.join("\n"));
+        assert_eq!(DayX.part1(&DayX.parse(&EXAMPLE.join("\n")).unwrap()), 3);
+    }
+
+    #[test]
+    fn part2_given() {
+        println!("{}", EXAMPLE.join("\n"));
+        assert_eq!(DayX.part2(&DayX.parse(&EXAMPLE.join("\n")).unwrap()), 4);
+    }
+
+    #[test]
+    fn answers() {
+        common::answers::verify(&DayX, env!("CARGO_MANIFEST_DIR"));
+    }
+}
@@ -0,0 +1,50 @@
+use common::Solution;
+use common::input;
+use common::parse::ParseError;
+
+#[derive(Default)]
+pub struct DayX;
+
+pub type Grid = Vec<Vec<char>>;
+
+impl Solution for DayX {
+    type Parsed = Grid;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Grid, ParseError> {
+        input::grid(input, "'#' or '.'", |c| matches!(c, '#' | '.').then_some(c))
+    }
+
+    fn part1(&self, grid: &Grid) -> usize {
+        grid.iter().flatten().filter(|c| **c == '#').count()
+    }
+
+    fn part2(&self, grid: &Grid) -> usize {
+        grid.iter().flatten().filter(|c| **c == '.').count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: [&str; 4] = ["#..#", ".##.", ".##.", "#..#"];
+
+    #[test]
+    fn part1_given() {
+        println!("{}", EXAMPLE.join("\n"));
+        assert_eq!(DayX.part1(&DayX.parse(&EXAMPLE.join("\n")).unwrap()), 8);
+    }
+
+    #[test]
+    fn part2_given() {
+        println!("{}", EXAMPLE.join("\n"));
+        assert_eq!(DayX.part2(&DayX.parse(&EXAMPLE.join("\n")).unwrap()), 8);
+    }
+
+    #[test]
+    fn answers() {
+        common::answers::verify(&DayX, env!("CARGO_MANIFEST_DIR"));
+    }
+}
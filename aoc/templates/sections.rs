@@ -0,0 +1,54 @@
+use common::Solution;
+use common::input;
+use common::parse::ParseError;
+
+#[derive(Default)]
+pub struct DayX;
+
+/// The input split into blocks of lines wherever there's a blank line
+pub type Sections = Vec<Vec<String>>;
+
+impl Solution for DayX {
+    type Parsed = Sections;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Sections, ParseError> {
+        Ok(input::sections(input)
+            .into_iter()
+            .map(|section| section.iter().map(|line| line.text.to_string()).collect())
+            .collect())
+    }
+
+    fn part1(&self, sections: &Sections) -> usize {
+        sections.len()
+    }
+
+    fn part2(&self, sections: &Sections) -> usize {
+        sections.iter().map(|s| s.len()).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: [&str; 6] = ["3-5", "10-14", "", "1", "5", "8"];
+
+    #[test]
+    fn part1_given() {
+        println!("{}", EXAMPLE.join("\n"));
+        assert_eq!(DayX.part1(&DayX.parse(&EXAMPLE.join("\n")).unwrap()), 2);
+    }
+
+    #[test]
+    fn part2_given() {
+        println!("{}", EXAMPLE.join("\n"));
+        assert_eq!(DayX.part2(&DayX.parse(&EXAMPLE.join("\n")).unwrap()), 5);
+    }
+
+    #[test]
+    fn answers() {
+        common::answers::verify(&DayX, env!("CARGO_MANIFEST_DIR"));
+    }
+}
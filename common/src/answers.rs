@@ -0,0 +1,192 @@
+use crate::{Answer, Part, Solution};
+use serde::{Deserialize, Deserializer};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// The manifest every day keeps next to its inputs
+pub const FILE: &str = "answers.toml";
+
+/// What a manifest records for an answer nobody knows yet, which still says the input is meant
+/// for that part
+pub const UNKNOWN: &str = "unk";
+
+/// Whether a recorded answer is a placeholder rather than something to check against
+pub fn is_unknown(answer: &str) -> bool {
+    answer.is_empty() || answer == UNKNOWN
+}
+
+/// One input file and the answers each part should give for it
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Case {
+    /// path to the input, relative to the day's directory
+    pub file: String,
+    #[serde(default, deserialize_with = "answer")]
+    pub part1: Option<String>,
+    #[serde(default, deserialize_with = "answer")]
+    pub part2: Option<String>,
+    pub note: Option<String>,
+}
+
+impl Case {
+    pub fn expected(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part1.as_deref(),
+            Part::Two => self.part2.as_deref(),
+        }
+    }
+
+    /// The answer to check a part against, if one's been recorded and it isn't `UNKNOWN`
+    pub fn known(&self, part: Part) -> Option<&str> {
+        self.expected(part).filter(|answer| !is_unknown(answer))
+    }
+
+    /// Whether a part should be run against this input at all
+    ///
+    /// An input with no answers yet is worth running for both parts, but one that only records
+    /// an answer for one part (an example from that part's text, say) is left out of the other.
+    pub fn covers(&self, part: Part) -> bool {
+        (self.part1.is_none() && self.part2.is_none()) || self.expected(part).is_some()
+    }
+}
+
+/// Answers can be written as TOML integers or, when they're too big for one, strings
+fn answer<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Raw {
+        Integer(i64),
+        Text(String),
+    }
+    Ok(
+        Option::<Raw>::deserialize(deserializer)?.map(|raw| match raw {
+            Raw::Integer(i) => i.to_string(),
+            Raw::Text(s) => s,
+        }),
+    )
+}
+
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Answers {
+    #[serde(default, rename = "input")]
+    pub cases: Vec<Case>,
+}
+
+impl Answers {
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        toml::from_str(contents).map_err(|e| e.to_string())
+    }
+
+    /// Read a day's manifest, treating a missing one as having no answers recorded
+    pub fn load(dir: &Path) -> io::Result<Self> {
+        let path = dir.join(FILE);
+        match fs::read_to_string(&path) {
+            Ok(contents) => Self::parse(&contents).map_err(|e| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}: {}", path.display(), e),
+                )
+            }),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Solve every input listed in a day's manifest, panicking with every wrong answer found
+///
+/// Meant to be called from a day's tests with `env!("CARGO_MANIFEST_DIR")`. Inputs that aren't
+/// on disk are skipped, since not everyone's puzzle input is committed.
+pub fn verify<S: Solution>(solution: &S, dir: &str) {
+    let dir = Path::new(dir);
+    let answers = Answers::load(dir).unwrap();
+    let mut wrong = Vec::new();
+    for case in answers.cases.iter() {
+        let Ok(input) = fs::read_to_string(dir.join(&case.file)) else {
+            eprintln!("skipping {}, it isn't on disk", case.file);
+            continue;
+        };
+        if Part::ALL.iter().all(|&part| case.known(part).is_none()) {
+            continue;
+        }
+        let parsed = match solution.parse(&input) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                wrong.push(format!("{} {}", case.file, e));
+                continue;
+            }
+        };
+        for part in Part::ALL {
+            let Some(expected) = case.known(part) else {
+                continue;
+            };
+            let answer = match part {
+                Part::One => solution.part1(&parsed).render(),
+                Part::Two => solution.part2(&parsed).render(),
+            };
+            let answer = match answer {
+                Ok(answer) => answer,
+                Err(e) => {
+                    wrong.push(format!("{} {}: {}", case.file, part, e));
+                    continue;
+                }
+            };
+            if answer != expected {
+                wrong.push(format!(
+                    "{} {}: got {}, expected {}",
+                    case.file, part, answer, expected
+                ));
+            }
+        }
+    }
+    assert!(wrong.is_empty(), "wrong answers:\n{}", wrong.join("\n"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest() {
+        let manifest = [
+            "[[input]]",
+            "file = \"input\"",
+            "part1 = 1059",
+            "part2 = \"27055852018812\"",
+            "",
+            "[[input]]",
+            "file = \"example\"",
+            "part1 = 3",
+            "note = \"from the puzzle text\"",
+        ];
+        println!("{}", manifest.join("\n"));
+        let answers = Answers::parse(&manifest.join("\n")).unwrap();
+        assert_eq!(answers.cases.len(), 2);
+        assert_eq!(answers.cases[0].expected(Part::One), Some("1059"));
+        assert_eq!(answers.cases[0].expected(Part::Two), Some("27055852018812"));
+        assert_eq!(answers.cases[1].expected(Part::Two), None);
+        assert!(!answers.cases[1].covers(Part::Two));
+        assert_eq!(answers.cases[1].known(Part::One), Some("3"));
+        assert_eq!(
+            answers.cases[1].note.as_deref(),
+            Some("from the puzzle text")
+        );
+    }
+
+    #[test]
+    fn unknown() {
+        let answers = Answers::parse("[[input]]\nfile = \"input\"\npart1 = \"unk\"\n").unwrap();
+        let case = &answers.cases[0];
+        assert_eq!(case.expected(Part::One), Some(UNKNOWN));
+        assert_eq!(case.known(Part::One), None);
+        assert!(case.covers(Part::One) && !case.covers(Part::Two));
+        assert!(is_unknown("") && !is_unknown("0"));
+    }
+
+    #[test]
+    fn typo() {
+        assert!(Answers::parse("[[input]]\nfile = \"input\"\npart3 = 1\n").is_err());
+    }
+}
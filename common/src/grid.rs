@@ -0,0 +1,264 @@
+use crate::input;
+use crate::parse::ParseError;
+use clap::ValueEnum;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// Which cells around a cell count as its neighbours
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+pub enum Shape {
+    /// every cell within `radius` steps counting diagonal ones, the 8 around it for radius 1
+    Moore,
+    /// every cell within `radius` steps up, down, left or right, the 4 beside it for radius 1
+    VonNeumann,
+}
+
+/// The cells around a cell, and whether they carry on over the edges of the grid
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Neighborhood {
+    pub shape: Shape,
+    pub radius: usize,
+    /// whether the grid wraps around, so cells off one edge are found at the other
+    pub wrap: bool,
+}
+
+impl Neighborhood {
+    pub const MOORE: Neighborhood = Neighborhood {
+        shape: Shape::Moore,
+        radius: 1,
+        wrap: false,
+    };
+    pub const VON_NEUMANN: Neighborhood = Neighborhood {
+        shape: Shape::VonNeumann,
+        radius: 1,
+        wrap: false,
+    };
+
+    /// How far each neighbour is from the cell in the middle, a row at a time
+    ///
+    /// These are worked out as they're needed rather than collected, as every call to
+    /// `Grid::neighbors` goes through them.
+    pub fn offsets(&self) -> impl Iterator<Item = (isize, isize)> + use<> {
+        let r = self.radius as isize;
+        let shape = self.shape;
+        (-r..=r)
+            .flat_map(move |dy| {
+                let reach = match shape {
+                    Shape::Moore => r,
+                    Shape::VonNeumann => r - dy.abs(),
+                };
+                (-reach..=reach).map(move |dx| (dx, dy))
+            })
+            .filter(|&offset| offset != (0, 0))
+    }
+}
+
+/// A rectangle of cells, addressed by `(x, y)` from the top left
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    /// row by row
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    /// A grid with every cell set to `fill`
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Load a grid from its characters, as `input::grid` does
+    pub fn parse(
+        input: &str,
+        expected: &str,
+        cell: impl Fn(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let rows = input::grid(input, expected, cell)?;
+        let width = rows.first().map_or(0, |row| row.len());
+        Ok(Self {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        (x < self.width && y < self.height).then(|| &self.cells[y * self.width + x])
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        (x < self.width && y < self.height).then(|| &mut self.cells[y * self.width + x])
+    }
+
+    /// Every position in the grid, a row at a time
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> + use<T> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Every cell along with its position, a row at a time
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(x).step_by(self.width.max(1))
+    }
+
+    /// The positions of the neighbours of `(x, y)`
+    ///
+    /// Off the edges there are no neighbours, unless the neighbourhood wraps around, in which
+    /// case a grid smaller than the neighbourhood will give some cells more than once, though
+    /// never `(x, y)` itself. An empty grid has nothing to wrap round to.
+    pub fn neighbors(
+        &self,
+        x: usize,
+        y: usize,
+        neighborhood: &Neighborhood,
+    ) -> impl Iterator<Item = (usize, usize)> + use<T> {
+        let (width, height) = (self.width as isize, self.height as isize);
+        let wrap = neighborhood.wrap && width > 0 && height > 0;
+        neighborhood
+            .offsets()
+            .filter_map(move |(dx, dy)| {
+                let (nx, ny) = (x as isize + dx, y as isize + dy);
+                if wrap {
+                    return Some((nx.rem_euclid(width), ny.rem_euclid(height)));
+                }
+                ((0..width).contains(&nx) && (0..height).contains(&ny)).then_some((nx, ny))
+            })
+            .map(|(nx, ny)| (nx as usize, ny as usize))
+            .filter(move |&at| at != (x, y))
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        self.get(x, y).unwrap_or_else(|| {
+            panic!(
+                "({}, {}) is outside a {}x{} grid",
+                x, y, self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(x, y)
+            .unwrap_or_else(|| panic!("({}, {}) is outside a {}x{} grid", x, y, width, height))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn letters() -> Grid<char> {
+        Grid::parse("abc\ndef\nghi\n", "a letter", |c| {
+            c.is_ascii_lowercase().then_some(c)
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn views() {
+        let grid = letters();
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid.get(2, 0), Some(&'c'));
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid[(0, 2)], 'g');
+        assert_eq!(grid.row(1), &['d', 'e', 'f']);
+        assert_eq!(grid.column(1).collect::<String>(), "beh");
+        assert_eq!(grid.rows().count(), 3);
+        assert_eq!(grid.to_string(), "abc\ndef\nghi");
+
+        let mut grid = grid;
+        grid[(1, 1)] = 'E';
+        assert_eq!(grid.iter().nth(4), Some(((1, 1), &'E')));
+        let error = Grid::parse("ab\nc\n", "a letter", Some).unwrap_err();
+        assert_eq!(error.expected, "a row 2 wide");
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = letters();
+        let around = |x, y, neighborhood| -> String {
+            grid.neighbors(x, y, &neighborhood)
+                .map(|(x, y)| grid[(x, y)])
+                .collect()
+        };
+        assert_eq!(around(1, 1, Neighborhood::MOORE), "abcdfghi");
+        assert_eq!(around(1, 1, Neighborhood::VON_NEUMANN), "bdfh");
+        assert_eq!(around(0, 0, Neighborhood::MOORE), "bde");
+        assert_eq!(around(0, 0, Neighborhood::VON_NEUMANN), "bd");
+        let wrapping = Neighborhood {
+            wrap: true,
+            ..Neighborhood::VON_NEUMANN
+        };
+        assert_eq!(around(0, 0, wrapping), "gcbd");
+
+        let far = Neighborhood {
+            radius: 2,
+            ..Neighborhood::VON_NEUMANN
+        };
+        assert_eq!(far.offsets().count(), 12);
+        assert_eq!(around(0, 0, far), "bcdeg");
+        let far = Neighborhood {
+            radius: 2,
+            ..Neighborhood::MOORE
+        };
+        assert_eq!(far.offsets().count(), 24);
+
+        // a grid narrower than the neighbourhood wraps back round to the cell itself, which
+        // isn't its own neighbour, and an empty one has nowhere to wrap to
+        let column = Grid::parse("a\nb\nc\n", "a letter", Some).unwrap();
+        let around: String = column
+            .neighbors(0, 1, &wrapping)
+            .map(|at| column[at])
+            .collect();
+        assert_eq!(around, "ac");
+        let empty: Grid<char> = Grid::new(0, 0, ' ');
+        assert_eq!(empty.neighbors(0, 0, &wrapping).count(), 0);
+    }
+}
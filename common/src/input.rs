@@ -0,0 +1,102 @@
+use crate::parse::{self, Line, ParseError};
+use std::any;
+use std::str::FromStr;
+
+/// The lines with something on them, numbered as they are in the file
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    parse::lines(input).filter(|line| !line.text.is_empty())
+}
+
+/// Runs of lines separated by one or more blank lines
+pub fn sections(input: &str) -> Vec<Vec<Line<'_>>> {
+    let mut sections = vec![Vec::new()];
+    for line in parse::lines(input) {
+        if line.text.is_empty() {
+            sections.push(Vec::new());
+        } else {
+            sections.last_mut().unwrap().push(line);
+        }
+    }
+    sections.retain(|s| !s.is_empty());
+    sections
+}
+
+/// Parse every line as a whole into a `T`
+pub fn typed<'a, T: FromStr>(
+    lines: impl IntoIterator<Item = Line<'a>>,
+) -> impl Iterator<Item = Result<T, ParseError>> {
+    lines.into_iter().map(|line| {
+        line.text.parse().map_err(|_| {
+            let name = any::type_name::<T>();
+            line.error(
+                line.text,
+                format!("a {}", name.rsplit("::").next().unwrap()),
+            )
+        })
+    })
+}
+
+/// Load a rectangular grid, turning each character into a cell with `cell`
+///
+/// `cell` returns None for characters that don't belong, which become an error saying the
+/// grid `expected` something else there.
+pub fn grid<T>(
+    input: &str,
+    expected: &str,
+    cell: impl Fn(char) -> Option<T>,
+) -> Result<Vec<Vec<T>>, ParseError> {
+    let mut grid: Vec<Vec<T>> = Vec::new();
+    for line in lines(input) {
+        let row = line
+            .chars()
+            .map(|(c, at)| cell(c).ok_or_else(|| line.error(at, expected)))
+            .collect::<Result<Vec<T>, _>>()?;
+        if let Some(first) = grid.first()
+            && row.len() != first.len()
+        {
+            return Err(line.error(line.text, format!("a row {} wide", first.len())));
+        }
+        grid.push(row);
+    }
+    Ok(grid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_sections() {
+        let input = "3-5\n10-14\n\n\n1\n5\n";
+        let sections = sections(input);
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[0].len(), 2);
+        assert_eq!(sections[1][0].number, 5);
+
+        let ids: Vec<usize> = typed(sections[1].clone())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(ids, vec![1, 5]);
+        let error = typed::<usize>(sections[0].clone())
+            .next()
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(error.expected, "a usize");
+    }
+
+    #[test]
+    fn load_grid() {
+        let cell = |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        };
+        let loaded = grid("#.\n.#\n", "'#' or '.'", cell).unwrap();
+        assert_eq!(loaded, vec![vec![true, false], vec![false, true]]);
+
+        let error = grid("#.\n.x\n", "'#' or '.'", cell).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        let error = grid("#.\n.##\n", "'#' or '.'", cell).unwrap_err();
+        assert_eq!(error.expected, "a row 2 wide");
+    }
+}
@@ -0,0 +1,116 @@
+pub mod answers;
+pub mod grid;
+pub mod input;
+pub mod output;
+pub mod parse;
+pub mod reference;
+
+use parse::ParseError;
+use std::any::Any;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// A single day's puzzle, parsed once and then solved for either part
+///
+/// Implementors hold any configuration the day needs (the number of connections to make, the
+/// size of a dial, ...) with `Default` giving the values the puzzle itself asks for.
+pub trait Solution {
+    /// The puzzle input after parsing, shared between both parts
+    type Parsed;
+    type Part1: Answer;
+    type Part2: Answer;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, ParseError>;
+    fn part1(&self, parsed: &Self::Parsed) -> Self::Part1;
+    fn part2(&self, parsed: &Self::Parsed) -> Self::Part2;
+}
+
+/// What a part hands back, rendered into the answer that gets printed
+///
+/// Most parts return their answer as it is, but a part that can find the input unusable when
+/// the other part doesn't (too short for what it's asked, say) returns a `Result`, with an
+/// error pointing at the line it couldn't use.
+pub trait Answer {
+    fn render(&self) -> Result<String, ParseError>;
+}
+
+macro_rules! answer {
+    ($($t:ty),*) => {
+        $(impl Answer for $t {
+            fn render(&self) -> Result<String, ParseError> {
+                Ok(self.to_string())
+            }
+        })*
+    };
+}
+
+answer!(usize, u32, u64, u128, i32, i64, String);
+
+impl<T: Answer> Answer for Result<T, ParseError> {
+    fn render(&self) -> Result<String, ParseError> {
+        self.as_ref().map_err(Clone::clone)?.render()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "part1"),
+            Part::Two => write!(f, "part2"),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "part1" => Ok(Part::One),
+            "part2" => Ok(Part::Two),
+            _ => Err(format!("'{}' is not a part (part1, part2)", s)),
+        }
+    }
+}
+
+/// A `Solution` with its types erased, so days can sit side by side in one registry
+pub trait Solver {
+    fn parse_any(&self, input: &str) -> Result<Box<dyn Any>, ParseError>;
+    /// Solve one part of the puzzle from the output of `parse_any`, rendering the answer
+    fn solve_any(&self, parsed: &dyn Any, part: Part) -> Result<String, ParseError>;
+
+    /// Parse and solve a single part in one go
+    fn solve(&self, input: &str, part: Part) -> Result<String, ParseError> {
+        self.solve_any(self.parse_any(input)?.as_ref(), part)
+    }
+}
+
+impl<S> Solver for S
+where
+    S: Solution,
+    S::Parsed: 'static,
+{
+    fn parse_any(&self, input: &str) -> Result<Box<dyn Any>, ParseError> {
+        Ok(Box::new(self.parse(input)?))
+    }
+
+    fn solve_any(&self, parsed: &dyn Any, part: Part) -> Result<String, ParseError> {
+        let parsed = parsed
+            .downcast_ref::<S::Parsed>()
+            .expect("parsed input came from a different solver");
+        match part {
+            Part::One => self.part1(parsed).render(),
+            Part::Two => self.part2(parsed).render(),
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use crate::parse::ParseError;
+use crate::{Part, Solver};
+use clap::ValueEnum;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::process::ExitCode;
+use std::time::Instant;
+
+/// How a part's binary prints its answer
+#[derive(Clone, Copy, Debug, Default, PartialEq, ValueEnum)]
+pub enum Format {
+    /// just the answer
+    #[default]
+    Text,
+    /// one JSON object per run, for scripts
+    Json,
+}
+
+/// Everything a `--format json` run prints
+#[derive(Debug, Serialize)]
+pub struct Report {
+    pub day: String,
+    pub part: String,
+    pub answer: String,
+    pub parse_ns: u64,
+    pub solve_ns: u64,
+    /// size of the rayon pool the part ran in
+    pub threads: usize,
+    /// hex SHA-256 of the input, matching `sha256sum`
+    pub input_sha256: String,
+}
+
+pub fn sha256(input: &str) -> String {
+    Sha256::digest(input.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Solve a part, timing the parse and the solve separately
+pub fn solve(
+    solver: &dyn Solver,
+    day: &str,
+    part: Part,
+    input: &str,
+) -> Result<Report, ParseError> {
+    let start = Instant::now();
+    let parsed = solver.parse_any(input)?;
+    let parsed_at = Instant::now();
+    let answer = solver.solve_any(parsed.as_ref(), part)?;
+    let solved_at = Instant::now();
+    Ok(Report {
+        day: day.to_string(),
+        part: part.to_string(),
+        answer,
+        parse_ns: (parsed_at - start).as_nanos() as u64,
+        solve_ns: (solved_at - parsed_at).as_nanos() as u64,
+        threads: rayon::current_num_threads(),
+        input_sha256: sha256(input),
+    })
+}
+
+impl Report {
+    pub fn json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+}
+
+/// Solve a part and print it in `format`, or print a diagnostic pointing at the bad input
+pub fn report(solver: &dyn Solver, day: &str, part: Part, input: &str, format: Format) -> ExitCode {
+    match solve(solver, day, part, input) {
+        Ok(report) => {
+            match format {
+                Format::Text => println!("{}", report.answer),
+                Format::Json => println!("{}", report.json()),
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprint!("{}", e.diagnostic(input));
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash() {
+        assert_eq!(
+            sha256("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
@@ -0,0 +1,165 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// Why an input couldn't be parsed, pointing at where in it things went wrong
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    /// 1-based line number, one past the last line for errors at the end of the input
+    pub line: usize,
+    /// 1-based column, counted in characters
+    pub column: usize,
+    pub expected: String,
+    /// the text that was there instead, empty if there was nothing
+    pub found: String,
+}
+
+impl ParseError {
+    /// An error for input that stopped before something it needed
+    pub fn end(input: &str, expected: impl Into<String>) -> Self {
+        Self {
+            line: input.lines().count() + 1,
+            column: 1,
+            expected: expected.into(),
+            found: String::new(),
+        }
+    }
+
+    /// Render the error with the offending line underneath it, the way rustc does
+    pub fn diagnostic(&self, input: &str) -> String {
+        let mut ret = format!("error: {}\n", self);
+        let Some(text) = input.lines().nth(self.line - 1) else {
+            return ret;
+        };
+        let gutter = " ".repeat(self.line.to_string().len());
+        let width = self.found.chars().count().max(1);
+        ret += &format!("{} |\n", gutter);
+        ret += &format!("{} | {}\n", self.line, text);
+        ret += &format!(
+            "{} | {}{}\n",
+            gutter,
+            " ".repeat(self.column - 1),
+            "^".repeat(width)
+        );
+        ret
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found ",
+            self.line, self.column, self.expected
+        )?;
+        if self.found.is_empty() {
+            write!(f, "nothing")
+        } else {
+            write!(f, "'{}'", self.found)
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// A line of the input that knows where it came from, so errors can point back at it
+#[derive(Clone, Copy, Debug)]
+pub struct Line<'a> {
+    /// 1-based line number
+    pub number: usize,
+    pub text: &'a str,
+}
+
+/// Every line of the input, blank ones included so the numbering matches the file
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input.lines().enumerate().map(|(i, text)| Line {
+        number: i + 1,
+        text,
+    })
+}
+
+impl<'a> Line<'a> {
+    /// An error pointing at `at`, which should be a slice of this line's text
+    pub fn error(&self, at: &str, expected: impl Into<String>) -> ParseError {
+        ParseError {
+            line: self.number,
+            column: self.column(at),
+            expected: expected.into(),
+            found: at.to_string(),
+        }
+    }
+
+    /// Parse `field`, a slice of this line's text, as a number
+    pub fn number<T: FromStr>(&self, field: &str) -> Result<T, ParseError> {
+        field.parse().map_err(|_| self.error(field, "a number"))
+    }
+
+    /// Each character of the line alongside the slice it covers, to hand to `error`
+    pub fn chars(&self) -> impl Iterator<Item = (char, &'a str)> + use<'a> {
+        let text = self.text;
+        text.char_indices()
+            .map(move |(i, c)| (c, &text[i..i + c.len_utf8()]))
+    }
+
+    /// The empty slice at the end of the line, for complaining that something is missing
+    pub fn end(&self) -> &'a str {
+        &self.text[self.text.len()..]
+    }
+
+    fn column(&self, at: &str) -> usize {
+        let offset = (at.as_ptr() as usize).wrapping_sub(self.text.as_ptr() as usize);
+        if offset > self.text.len() {
+            return 1;
+        }
+        self.text[..offset].chars().count() + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn columns() {
+        let input = "R12\nL4x\n\nÜ9";
+        let lines: Vec<Line> = lines(input).collect();
+        assert_eq!(lines.len(), 4);
+
+        let error = lines[1].number::<isize>(&lines[1].text[1..]).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.found, "4x");
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 2: expected a number, found '4x'"
+        );
+
+        let (_, digit) = lines[3].chars().nth(1).unwrap();
+        assert_eq!(lines[3].error(digit, "a letter").column, 2);
+        assert_eq!(lines[3].error(lines[3].end(), "a letter").column, 3);
+    }
+
+    #[test]
+    fn diagnostic() {
+        let input = "R12\nL4x\n";
+        let line = lines(input).nth(1).unwrap();
+        let error = line.number::<isize>(&line.text[1..]).unwrap_err();
+        assert_eq!(
+            error.diagnostic(input),
+            [
+                "error: line 2, column 2: expected a number, found '4x'",
+                "  |",
+                "2 | L4x",
+                "  |  ^^",
+                "",
+            ]
+            .join("\n")
+        );
+
+        let end = ParseError::end(input, "a start 'S'");
+        assert_eq!(end.line, 3);
+        assert_eq!(
+            end.diagnostic(input),
+            "error: line 3, column 1: expected a start 'S', found nothing\n"
+        );
+    }
+}
@@ -0,0 +1,137 @@
+use crate::answers::Answers;
+use crate::{Answer, Part, Solution};
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// A small deterministic generator, so inputs made up for differential tests are repeatable
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        // xorshift gets stuck on zero
+        Self(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// A number in `0..n`
+    pub fn below(&mut self, n: u64) -> u64 {
+        self.next_u64() % n
+    }
+}
+
+fn has_python() -> bool {
+    Command::new("python3")
+        .arg("--version")
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+/// Run a reference script against `input`
+///
+/// The scripts read a file called `input` beside themselves, so each run gets a scratch
+/// directory holding a copy of the script and the input.
+pub fn run_script(script: &Path, input: &str, scratch: &Path) -> io::Result<String> {
+    fs::create_dir_all(scratch)?;
+    let copy = scratch.join(script.file_name().unwrap());
+    fs::copy(script, &copy)?;
+    fs::write(scratch.join("input"), input)?;
+    let output = Command::new("python3").arg(&copy).output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "{} failed: {}",
+            script.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Check a day's reference script agrees with its solution for a part
+///
+/// Every input in the day's manifest that's on disk is tried, along with the `generated` ones,
+/// panicking with each disagreement found. Meant to be called from a day's tests with
+/// `env!("CARGO_MANIFEST_DIR")`, and skips itself when there's no `python3` to run.
+pub fn check<S: Solution>(solution: &S, part: Part, dir: &str, script: &str, generated: &[String]) {
+    if !has_python() {
+        eprintln!("skipping {}, python3 isn't available", script);
+        return;
+    }
+    let dir = Path::new(dir);
+    let mut inputs: Vec<(String, String)> = Vec::new();
+    for case in Answers::load(dir).unwrap().cases {
+        if !case.covers(part) {
+            continue;
+        }
+        if let Ok(input) = fs::read_to_string(dir.join(&case.file)) {
+            inputs.push((case.file, input));
+        }
+    }
+    for (i, input) in generated.iter().enumerate() {
+        inputs.push((format!("generated #{}", i), input.clone()));
+    }
+
+    let scratch = scratch_dir(dir, script);
+    let mut disagreements = Vec::new();
+    for (name, input) in inputs.iter() {
+        let expected = run_script(&dir.join(script), input, &scratch)
+            .unwrap_or_else(|e| panic!("{}: {}", name, e));
+        let answer = solution.parse(input).and_then(|parsed| match part {
+            Part::One => solution.part1(&parsed).render(),
+            Part::Two => solution.part2(&parsed).render(),
+        });
+        let answer = answer.unwrap_or_else(|e| e.to_string());
+        if answer != expected {
+            disagreements.push(format!(
+                "{}: {} says {}, the solution says {}",
+                name, script, expected, answer
+            ));
+        }
+    }
+    fs::remove_dir_all(&scratch).ok();
+    assert!(
+        disagreements.is_empty(),
+        "disagreements with {}:\n{}",
+        script,
+        disagreements.join("\n")
+    );
+}
+
+/// Somewhere for a script's runs to happen that won't collide with other tests running at once
+fn scratch_dir(dir: &Path, script: &str) -> PathBuf {
+    let day = dir.file_name().unwrap().to_string_lossy();
+    env::temp_dir().join(format!(
+        "reference-{}-{}-{}",
+        day,
+        script,
+        std::process::id()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rng() {
+        // the same seed gives the same draws, which move on each time
+        let (mut rng, mut again) = (Rng::new(7), Rng::new(7));
+        let draws: Vec<u64> = (0..100).map(|_| rng.next_u64()).collect();
+        assert!(draws.iter().all(|&d| d == again.next_u64()));
+        assert!(draws.windows(2).all(|pair| pair[0] != pair[1]));
+        let mut other = Rng::new(8);
+        assert!(draws.iter().any(|&d| d != other.next_u64()));
+
+        let below: Vec<u64> = (0..100).map(|_| rng.below(10)).collect();
+        assert!(below.iter().all(|&d| d < 10));
+        assert!(below.iter().any(|&d| d != below[0]));
+    }
+}
@@ -1,55 +1,56 @@
-use clap::Parser;
+use clap::{CommandFactory, Parser, error::ErrorKind};
 use clio::Input;
-use std::io::{self, BufReader, prelude::*};
+use common::output::{Format, report};
+use common::{Part, Solution};
+use day01::{Day01, Dial, Trace};
+use std::io::{self, prelude::*};
+use std::process::ExitCode;
 
 #[derive(Parser)]
 struct Opt {
     /// Input file, use '-' for stdin
     #[clap(value_parser, default_value = "-")]
     input: Input,
-}
 
-fn solve(lines: Vec<String>) -> usize {
-    let mut dial: isize = 50;
-    let mut ret = 0;
-    for line in lines.iter() {
-        let direction = line.chars().nth(0).unwrap();
-        let mut num = line[1..line.len()].parse::<isize>().unwrap();
-        if direction == 'L' {
-            num = -num;
-        }
-        dial = (dial + num) % 100;
-        if dial == 0 {
-            ret += 1;
-        }
-    }
+    /// how to print the answer
+    #[clap(long, value_enum, default_value_t)]
+    format: Format,
 
-    ret
-}
+    /// the number of positions on the dial
+    #[clap(long, default_value_t = Dial::default().modulus)]
+    modulus: usize,
 
-fn main() -> io::Result<()> {
-    let opt = Opt::parse();
+    /// the position the dial starts at
+    #[clap(long, default_value_t = Dial::default().start)]
+    start: usize,
 
-    let reader = BufReader::new(opt.input);
-    let lines: Vec<String> = reader.lines().flatten().filter(|s| !s.is_empty()).collect();
-    println!("{}", solve(lines));
-    Ok(())
-}
+    /// the position that counts towards the password
+    #[clap(long, default_value_t = Dial::default().target)]
+    target: usize,
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    fn convert_example(example: &[&str]) -> Vec<String> {
-        example.iter().map(|line| line.to_string()).collect()
-    }
+    /// write what every rotation did to stderr
+    #[clap(long, value_enum)]
+    trace: Option<Trace>,
+}
 
-    #[test]
-    fn given() {
-        let example = [
-            "L68", "L30", "R48", "L5", "R60", "L55", "L1", "L99", "R14", "L82",
-        ];
-        println!("{}", example.join("\n"));
-        assert_eq!(solve(convert_example(&example)), 3);
+fn main() -> io::Result<ExitCode> {
+    let mut opt = Opt::parse();
+    let dial = Dial::new(opt.modulus, opt.start, opt.target)
+        .unwrap_or_else(|e| Opt::command().error(ErrorKind::ValueValidation, e).exit());
+
+    let mut input = String::new();
+    opt.input.read_to_string(&mut input)?;
+    let day = Day01 { dial };
+    if let Some(format) = opt.trace
+        && let Ok(rotations) = day.parse(&input)
+    {
+        day01::trace(&mut io::stderr().lock(), format, &dial, &rotations)?;
     }
+    Ok(report(
+        &day,
+        env!("CARGO_PKG_NAME"),
+        Part::One,
+        &input,
+        opt.format,
+    ))
 }
@@ -1,98 +1,56 @@
-use clap::Parser;
+use clap::{CommandFactory, Parser, error::ErrorKind};
 use clio::Input;
-use std::io::{self, BufReader, prelude::*};
+use common::output::{Format, report};
+use common::{Part, Solution};
+use day01::{Day01, Dial, Trace};
+use std::io::{self, prelude::*};
+use std::process::ExitCode;
 
 #[derive(Parser)]
 struct Opt {
     /// Input file, use '-' for stdin
     #[clap(value_parser, default_value = "-")]
     input: Input,
-}
 
-fn solve(lines: Vec<String>) -> isize {
-    let mut dial = 50;
-    //eprintln!("Starting at {}", dial);
-    let mut ret = 0;
-    for line in lines.iter() {
-        let direction = line.chars().nth(0).unwrap();
-        let mut num = line[1..line.len()].parse::<isize>().unwrap();
-        if direction == 'L' {
-            //eprintln!("Moving Left by {}", num);
-            num = -num;
-            if dial + num <= 0 {
-                let mut start = 0;
-                if dial != 0 {
-                    start = 1;
-                }
-                let sweeps = start + ((dial + num) / 100).abs();
-                if sweeps != 0 {
-                    //eprintln!("Swept by, or landed on, 0 {} times", sweeps);
-                }
-                ret += sweeps;
-            }
-        } else {
-            //eprintln!("Moving Right by {}", num);
-            let sweeps = (dial + num) / 100;
-            if sweeps != 0 {
-                //eprintln!("Swept by, or landed on, 0 {} times", sweeps);
-            }
-            ret += sweeps;
-        }
-        dial = (dial + num).rem_euclid(100);
-        //eprintln!("Landed on {}", dial);
-    }
-    ret
-}
+    /// how to print the answer
+    #[clap(long, value_enum, default_value_t)]
+    format: Format,
 
-fn main() -> io::Result<()> {
-    let opt = Opt::parse();
+    /// the number of positions on the dial
+    #[clap(long, default_value_t = Dial::default().modulus)]
+    modulus: usize,
 
-    let reader = BufReader::new(opt.input);
-    let lines: Vec<String> = reader.lines().flatten().filter(|s| !s.is_empty()).collect();
-    println!("{}", solve(lines));
-    Ok(())
-}
+    /// the position the dial starts at
+    #[clap(long, default_value_t = Dial::default().start)]
+    start: usize,
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// the position that counts towards the password
+    #[clap(long, default_value_t = Dial::default().target)]
+    target: usize,
 
-    fn convert_example(example: &[&str]) -> Vec<String> {
-        example.iter().map(|line| line.to_string()).collect()
-    }
+    /// write what every rotation did to stderr
+    #[clap(long, value_enum)]
+    trace: Option<Trace>,
+}
 
-    #[test]
-    fn given() {
-        let example = [
-            "L68", "L30", "R48", "L5", "R60", "L55", "L1", "L99", "R14", "L82",
-        ];
-        println!("{}", example.join("\n"));
-        assert_eq!(solve(convert_example(&example)), 6);
-    }
-    #[test]
-    fn extra() {
-        let example = [
-            "L68", "L30", "R48", "L5", "R60", "L55", "L1", "L99", "R14", "L82", "R1000", "L1000",
-        ];
-        println!("{}", example.join("\n"));
-        assert_eq!(solve(convert_example(&example)), 26);
-    }
-    #[test]
-    fn input_one() {
-        let example = ["L23", "R14", "L50"];
-        println!("{}", example.join("\n"));
-        assert_eq!(solve(convert_example(&example)), 1);
-    }
-    #[test]
-    fn input_two() {
-        let example = ["L23", "R14", "L50", "R27"];
-        println!("{}", example.join("\n"));
-        assert_eq!(solve(convert_example(&example)), 2);
-    }
-    #[test]
-    fn right_sweeps() {
-        let example = ["L23", "R14", "L50", "R27", "L18", "R437"];
-        println!("{}", example.join("\n"));
-        assert_eq!(solve(convert_example(&example)), 7);
+fn main() -> io::Result<ExitCode> {
+    let mut opt = Opt::parse();
+    let dial = Dial::new(opt.modulus, opt.start, opt.target)
+        .unwrap_or_else(|e| Opt::command().error(ErrorKind::ValueValidation, e).exit());
+
+    let mut input = String::new();
+    opt.input.read_to_string(&mut input)?;
+    let day = Day01 { dial };
+    if let Some(format) = opt.trace
+        && let Ok(rotations) = day.parse(&input)
+    {
+        day01::trace(&mut io::stderr().lock(), format, &dial, &rotations)?;
     }
+    Ok(report(
+        &day,
+        env!("CARGO_PKG_NAME"),
+        Part::Two,
+        &input,
+        opt.format,
+    ))
 }
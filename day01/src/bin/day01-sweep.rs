@@ -0,0 +1,74 @@
+use clap::{CommandFactory, Parser, error::ErrorKind};
+use clio::Input;
+use common::Solution;
+use common::output::Format;
+use day01::{Day01, Dial, Password};
+use serde::Serialize;
+use std::io::{self, prelude::*};
+use std::process::ExitCode;
+
+/// Both passwords for every position the dial could start at
+#[derive(Parser)]
+struct Opt {
+    /// Input file, use '-' for stdin
+    #[clap(value_parser, default_value = "-")]
+    input: Input,
+
+    /// print a table, or JSON for scripts
+    #[clap(long, value_enum, default_value_t)]
+    format: Format,
+
+    /// the number of positions on the dial
+    #[clap(long, default_value_t = Dial::default().modulus)]
+    modulus: usize,
+
+    /// the position that counts towards the password
+    #[clap(long, default_value_t = Dial::default().target)]
+    target: usize,
+}
+
+#[derive(Serialize)]
+struct Row {
+    start: usize,
+    #[serde(flatten)]
+    password: Password,
+}
+
+fn main() -> io::Result<ExitCode> {
+    let mut opt = Opt::parse();
+    let dial = Dial::new(opt.modulus, 0, opt.target)
+        .unwrap_or_else(|e| Opt::command().error(ErrorKind::ValueValidation, e).exit());
+
+    let mut input = String::new();
+    opt.input.read_to_string(&mut input)?;
+    let day = Day01 { dial };
+    let rotations = match day.parse(&input) {
+        Ok(rotations) => rotations,
+        Err(e) => {
+            eprint!("{}", e.diagnostic(&input));
+            return Ok(ExitCode::FAILURE);
+        }
+    };
+    let rows: Vec<Row> = dial
+        .sweep(&rotations)
+        .into_iter()
+        .enumerate()
+        .map(|(start, password)| Row { start, password })
+        .collect();
+
+    let mut out = io::stdout().lock();
+    match opt.format {
+        Format::Text => {
+            writeln!(out, "{:>6} {:>8} {:>8}", "start", "part1", "part2")?;
+            for row in rows.iter() {
+                writeln!(
+                    out,
+                    "{:>6} {:>8} {:>8}",
+                    row.start, row.password.landed, row.password.passed
+                )?;
+            }
+        }
+        Format::Json => writeln!(out, "{}", serde_json::to_string(&rows).unwrap())?,
+    }
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,455 @@
+use clap::ValueEnum;
+use common::Solution;
+use common::input;
+use common::parse::ParseError;
+use serde::Serialize;
+use std::fmt;
+use std::io::{self, Write};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Direction {
+    Left,
+    Right,
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Direction::Left => write!(f, "L"),
+            Direction::Right => write!(f, "R"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rotation {
+    /// the input line it came from
+    pub line: usize,
+    pub direction: Direction,
+    pub clicks: usize,
+}
+
+impl Rotation {
+    /// The clicks as a signed turn, right being positive
+    fn by(&self) -> isize {
+        match self.direction {
+            Direction::Left => -(self.clicks as isize),
+            Direction::Right => self.clicks as isize,
+        }
+    }
+}
+
+/// What a single rotation did to the dial
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DialEvent {
+    pub line: usize,
+    pub from: usize,
+    pub to: usize,
+    pub direction: Direction,
+    /// how many clicks pointed the dial at the target, zero on the puzzle's dial, landing included
+    pub zero_crossings: usize,
+}
+
+/// A combination lock's dial: how many positions it has, where it starts and which one counts
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Dial {
+    pub modulus: usize,
+    pub start: usize,
+    pub target: usize,
+}
+
+impl Default for Dial {
+    fn default() -> Self {
+        Self {
+            modulus: 100,
+            start: 50,
+            target: 0,
+        }
+    }
+}
+
+/// Both ways of reading the password off a dial
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize)]
+pub struct Password {
+    /// how many rotations ended on the target
+    pub landed: usize,
+    /// how many clicks, over every rotation, pointed the dial at the target
+    pub passed: usize,
+}
+
+impl Dial {
+    pub fn new(modulus: usize, start: usize, target: usize) -> Result<Self, String> {
+        if modulus == 0 {
+            return Err("a dial needs at least one position".to_string());
+        }
+        if start >= modulus || target >= modulus {
+            return Err(format!(
+                "the start ({}) and target ({}) must be positions on the dial (0 to {})",
+                start,
+                target,
+                modulus - 1
+            ));
+        }
+        Ok(Self {
+            modulus,
+            start,
+            target,
+        })
+    }
+
+    /// Where the dial ends up after turning `by` clicks from `from`, right being positive
+    fn turn(&self, from: usize, by: isize) -> usize {
+        (from as isize + by).rem_euclid(self.modulus as isize) as usize
+    }
+
+    /// How many of the clicks turning `by` from `from` point the dial at the target
+    fn hits(&self, from: usize, by: isize) -> usize {
+        let modulus = self.modulus as isize;
+        let (from, target) = (from as isize, self.target as isize);
+        let distance = if by >= 0 {
+            (target - from).rem_euclid(modulus)
+        } else {
+            (from - target).rem_euclid(modulus)
+        };
+        // already sitting on the target means going all the way round to see it again
+        let first = if distance == 0 { modulus } else { distance };
+        if by.abs() < first {
+            0
+        } else {
+            ((by.abs() - first) / modulus + 1) as usize
+        }
+    }
+
+    /// Turn the dial through every rotation in turn, saying what each one did
+    pub fn events<'a>(&'a self, rotations: &'a [Rotation]) -> impl Iterator<Item = DialEvent> + 'a {
+        rotations.iter().scan(self.start, |dial, rotation| {
+            let from = *dial;
+            *dial = self.turn(from, rotation.by());
+            Some(DialEvent {
+                line: rotation.line,
+                from,
+                to: *dial,
+                direction: rotation.direction,
+                zero_crossings: self.hits(from, rotation.by()),
+            })
+        })
+    }
+
+    /// Turn the dial through every rotation, reading both passwords as it goes
+    ///
+    /// This is what adding up `events` gives, but as it's what both parts run it keeps to one
+    /// division a rotation. The dial is tracked as how far right of the target it is, so a
+    /// right turn passes the target once for each time it goes round past zero, and a left
+    /// turn is the same thing measured the other way round the dial.
+    pub fn spin(&self, rotations: &[Rotation]) -> Password {
+        let modulus = self.modulus;
+        let flip = |at: usize| if at == 0 { 0 } else { modulus - at };
+        let mut at = (self.start + modulus - self.target) % modulus;
+        let mut ret = Password::default();
+        for rotation in rotations {
+            at = match rotation.direction {
+                Direction::Right => {
+                    ret.passed += (at + rotation.clicks) / modulus;
+                    (at + rotation.clicks) % modulus
+                }
+                Direction::Left => {
+                    let from = flip(at);
+                    ret.passed += (from + rotation.clicks) / modulus;
+                    flip((from + rotation.clicks) % modulus)
+                }
+            };
+            if at == 0 {
+                ret.landed += 1;
+            }
+        }
+        ret
+    }
+}
+
+impl Dial {
+    /// Both passwords for every start position at once, indexed by start
+    ///
+    /// Where the dial is after each rotation is just the start plus a running total, so rather
+    /// than spinning once per start this works out, rotation by rotation, which starts would
+    /// land on or pass the target and tallies them all in one pass.
+    pub fn sweep(&self, rotations: &[Rotation]) -> Vec<Password> {
+        let modulus = self.modulus as isize;
+        let target = self.target as isize;
+        let mut landed = vec![0; self.modulus];
+        // counts where the partial turn at the end of a rotation passes the target, kept as
+        // differences so each rotation marks a whole run of starts in constant time
+        let mut partial = vec![0isize; self.modulus + 1];
+        let mut full_turns = 0;
+        let mut total: isize = 0;
+        for rotation in rotations.iter() {
+            full_turns += rotation.clicks / self.modulus;
+            let rest = (rotation.clicks % self.modulus) as isize;
+            if rest > 0 {
+                // the positions this rotation can start from and still reach the target
+                let first = match rotation.direction {
+                    Direction::Right => target - rest,
+                    Direction::Left => target + 1,
+                };
+                let first = (first - total).rem_euclid(modulus) as usize;
+                let last = first + rest as usize;
+                if last <= self.modulus {
+                    partial[first] += 1;
+                    partial[last] -= 1;
+                } else {
+                    partial[first] += 1;
+                    partial[self.modulus] -= 1;
+                    partial[0] += 1;
+                    partial[last - self.modulus] -= 1;
+                }
+            }
+            total += rotation.by();
+            landed[(target - total).rem_euclid(modulus) as usize] += 1;
+        }
+        let mut passes = 0;
+        landed
+            .into_iter()
+            .zip(partial)
+            .map(|(landed, partial)| {
+                passes += partial;
+                Password {
+                    landed,
+                    passed: full_turns + passes as usize,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Formats the rotation-by-rotation events can be traced in
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum Trace {
+    Csv,
+}
+
+/// Write out every event turning the dial through `rotations`
+pub fn trace(
+    out: &mut impl Write,
+    format: Trace,
+    dial: &Dial,
+    rotations: &[Rotation],
+) -> io::Result<()> {
+    match format {
+        Trace::Csv => {
+            writeln!(out, "line,from,to,direction,zero_crossings")?;
+            for event in dial.events(rotations) {
+                writeln!(
+                    out,
+                    "{},{},{},{},{}",
+                    event.line, event.from, event.to, event.direction, event.zero_crossings
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[derive(Default)]
+pub struct Day01 {
+    pub dial: Dial,
+}
+
+pub type Rotations = Vec<Rotation>;
+
+impl Solution for Day01 {
+    type Parsed = Rotations;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Rotations, ParseError> {
+        input::lines(input)
+            .map(|line| {
+                let (direction, at) = line.chars().next().unwrap();
+                let direction = match direction {
+                    'L' => Direction::Left,
+                    'R' => Direction::Right,
+                    _ => return Err(line.error(at, "'L' or 'R'")),
+                };
+                Ok(Rotation {
+                    line: line.number,
+                    direction,
+                    clicks: line.number(&line.text[at.len()..])?,
+                })
+            })
+            .collect()
+    }
+
+    fn part1(&self, rotations: &Rotations) -> usize {
+        self.dial.spin(rotations).landed
+    }
+
+    fn part2(&self, rotations: &Rotations) -> usize {
+        self.dial.spin(rotations).passed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Part;
+    use common::reference::{self, Rng};
+
+    const EXAMPLE: [&str; 10] = [
+        "L68", "L30", "R48", "L5", "R60", "L55", "L1", "L99", "R14", "L82",
+    ];
+
+    #[test]
+    fn part1_given() {
+        println!("{}", EXAMPLE.join("\n"));
+        let day = Day01::default();
+        assert_eq!(day.part1(&day.parse(&EXAMPLE.join("\n")).unwrap()), 3);
+    }
+
+    #[test]
+    fn part2_given() {
+        println!("{}", EXAMPLE.join("\n"));
+        let day = Day01::default();
+        assert_eq!(day.part2(&day.parse(&EXAMPLE.join("\n")).unwrap()), 6);
+    }
+    #[test]
+    fn part2_extra() {
+        let example = [
+            "L68", "L30", "R48", "L5", "R60", "L55", "L1", "L99", "R14", "L82", "R1000", "L1000",
+        ];
+        println!("{}", example.join("\n"));
+        let day = Day01::default();
+        assert_eq!(day.part2(&day.parse(&example.join("\n")).unwrap()), 26);
+    }
+    #[test]
+    fn part2_input_one() {
+        let example = ["L23", "R14", "L50"];
+        println!("{}", example.join("\n"));
+        let day = Day01::default();
+        assert_eq!(day.part2(&day.parse(&example.join("\n")).unwrap()), 1);
+    }
+    #[test]
+    fn part2_input_two() {
+        let example = ["L23", "R14", "L50", "R27"];
+        println!("{}", example.join("\n"));
+        let day = Day01::default();
+        assert_eq!(day.part2(&day.parse(&example.join("\n")).unwrap()), 2);
+    }
+    #[test]
+    fn part2_right_sweeps() {
+        let example = ["L23", "R14", "L50", "R27", "L18", "R437"];
+        println!("{}", example.join("\n"));
+        let day = Day01::default();
+        assert_eq!(day.part2(&day.parse(&example.join("\n")).unwrap()), 7);
+    }
+    #[test]
+    fn other_dials() {
+        let rotations = Day01::default().parse(&EXAMPLE.join("\n")).unwrap();
+        let day = Day01 {
+            dial: Dial::new(10, 0, 2).unwrap(),
+        };
+        // L68 from 0 passes 2 seven times on the way to landing on it, L30 goes round three more
+        assert_eq!(
+            day.dial.spin(&rotations[..2]),
+            Password {
+                landed: 2,
+                passed: 10
+            }
+        );
+        assert_eq!(day.part1(&rotations), 3);
+        assert_eq!(day.part2(&rotations), 47);
+        assert!(Dial::new(10, 10, 3).is_err());
+        assert!(Dial::new(0, 0, 0).is_err());
+    }
+
+    #[test]
+    fn sweep() {
+        let day = Day01::default();
+        let rotations = day.parse(&EXAMPLE.join("\n")).unwrap();
+        let sweep = day.dial.sweep(&rotations);
+        assert_eq!(sweep.len(), 100);
+        assert_eq!(
+            sweep[50],
+            Password {
+                landed: 3,
+                passed: 6
+            }
+        );
+
+        for seed in 0..5 {
+            let rotations = day.parse(&generate(seed)).unwrap();
+            for (modulus, target) in [(100, 0), (7, 3), (1, 0)] {
+                let sweep = Dial::new(modulus, 0, target).unwrap().sweep(&rotations);
+                for (start, password) in sweep.into_iter().enumerate() {
+                    let dial = Dial::new(modulus, start, target).unwrap();
+                    assert_eq!(password, dial.spin(&rotations), "{:?}", dial);
+                    let events = dial.events(&rotations);
+                    let passed: usize = events.map(|event| event.zero_crossings).sum();
+                    assert_eq!(password.passed, passed, "{:?}", dial);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn trace_csv() {
+        let example = ["L68", "", "R32", "L200"];
+        let day = Day01::default();
+        let rotations = day.parse(&example.join("\n")).unwrap();
+        let mut out = Vec::new();
+        trace(&mut out, Trace::Csv, &day.dial, &rotations).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            [
+                "line,from,to,direction,zero_crossings",
+                "1,50,82,L,1",
+                "3,82,14,R,1",
+                "4,14,14,L,2",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn parse_error() {
+        let example = ["L68", "", "X30", "R4a"];
+        println!("{}", example.join("\n"));
+        let error = Day01::default().parse(&example.join("\n")).unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+        assert_eq!(error.expected, "'L' or 'R'");
+        let error = Day01::default()
+            .parse(&example[3..].join("\n"))
+            .unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.found.as_str()),
+            (1, 2, "4a")
+        );
+    }
+
+    #[test]
+    fn answers() {
+        common::answers::verify(&Day01::default(), env!("CARGO_MANIFEST_DIR"));
+    }
+
+    fn generate(seed: u64) -> String {
+        let mut rng = Rng::new(seed);
+        (0..200)
+            .map(|_| {
+                let direction = if rng.below(2) == 0 { 'L' } else { 'R' };
+                format!("{}{}", direction, 1 + rng.below(999))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn reference_part1() {
+        let generated: Vec<String> = (0..5).map(generate).collect();
+        reference::check(
+            &Day01::default(),
+            Part::One,
+            env!("CARGO_MANIFEST_DIR"),
+            "part1.py",
+            &generated,
+        );
+    }
+}
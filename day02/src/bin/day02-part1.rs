@@ -1,71 +1,90 @@
 use clap::Parser;
 use clio::Input;
-use std::io::{self, BufReader, prelude::*};
+use common::output::{Format, report};
+use common::{Part, Solution};
+use day02::{Audit, Day02, Id, Overlaps, Policy, Repeats, Width, audit};
+use std::io::{self, prelude::*};
+use std::process::ExitCode;
 
 #[derive(Parser)]
 struct Opt {
     /// Input file, use '-' for stdin
     #[clap(value_parser, default_value = "-")]
     input: Input,
-}
 
-fn valid_id(id: usize) -> bool {
-    let len = id.ilog10() + 1;
-    if len % 2 == 1 {
-        return true;
-    }
-    let middle_pow = 10usize.pow(len / 2);
-    let (l, r) = (id / middle_pow, id % middle_pow);
-    if l == r {
-        return false;
-    }
-    return true;
-}
+    /// how to print the answer
+    #[clap(long, value_enum, default_value_t)]
+    format: Format,
 
-fn solve(ranges: Vec<&str>) -> usize {
-    let mut ret = 0;
-    for range in ranges {
-        let (start, end) = range.split_once('-').unwrap();
-        let u_start = start.parse::<usize>().unwrap();
-        let u_end = end.parse::<usize>().unwrap();
-        ret += (u_start..=u_end)
-            .into_iter()
-            .filter(|id| !valid_id(*id))
-            .sum::<usize>();
-    }
-    ret
-}
+    /// how many times a block of digits has to repeat to make an ID invalid
+    #[clap(long, default_value_t = Policy::HALVES.repeats)]
+    repeats: Repeats,
 
-fn main() -> io::Result<()> {
-    let opt = Opt::parse();
+    /// the fewest digits a repeating block can have
+    #[clap(long, default_value_t = Policy::HALVES.min_unit)]
+    min_unit: u32,
 
-    let mut reader = BufReader::new(opt.input);
-    let mut line = String::new();
-    reader.read_line(&mut line)?;
-    let ranges: Vec<&str> = line.split(',').map(|s| s.trim()).collect();
-    println!("{}", solve(ranges));
-    Ok(())
+    /// the base the IDs are written in
+    #[clap(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(2..=36))]
+    radix: u32,
+
+    /// how wide an integer to read the IDs into
+    #[clap(long, value_enum, default_value_t)]
+    width: Width,
+
+    /// count IDs in several ranges once for each (multiset) or just once (set)
+    #[clap(long, value_enum, default_value_t)]
+    overlaps: Overlaps,
+
+    /// list every invalid ID with how it repeats instead of the answer
+    #[clap(long, value_enum)]
+    report: Option<Audit>,
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    #[test]
-    fn given() {
-        let example = Vec::from([
-            "11-22",
-            "95-115",
-            "998-1012",
-            "1188511880-1188511890",
-            "222220-222224",
-            "1698522-1698528",
-            "446443-446449",
-            "38593856-38593862",
-            "565653-565659",
-            "824824821-824824827",
-            "2121212118-2121212124",
-        ]);
-        println!("{}", example.join(","));
-        assert_eq!(solve(example), 1227775554);
+fn main() -> io::Result<ExitCode> {
+    let mut opt = Opt::parse();
+
+    let mut input = String::new();
+    opt.input.read_to_string(&mut input)?;
+    match opt.width {
+        Width::U64 => solve::<u64>(&opt, &input),
+        Width::U128 => solve::<u128>(&opt, &input),
     }
 }
+
+fn solve<T: Id>(opt: &Opt, input: &str) -> io::Result<ExitCode> {
+    let day: Day02<T> = Day02 {
+        part1: Policy {
+            repeats: opt.repeats,
+            min_unit: opt.min_unit,
+        },
+        radix: opt.radix,
+        overlaps: opt.overlaps,
+        ..Day02::default()
+    };
+    let Some(format) = opt.report else {
+        return Ok(report(
+            &day,
+            env!("CARGO_PKG_NAME"),
+            Part::One,
+            input,
+            opt.format,
+        ));
+    };
+    let ranges = match day.parse(input) {
+        Ok(ranges) => ranges,
+        Err(e) => {
+            eprint!("{}", e.diagnostic(input));
+            return Ok(ExitCode::FAILURE);
+        }
+    };
+    audit(
+        &mut io::stdout().lock(),
+        format,
+        &ranges,
+        day.radix,
+        &day.part1,
+        day.overlaps,
+    )?;
+    Ok(ExitCode::SUCCESS)
+}
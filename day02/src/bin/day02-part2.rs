@@ -1,7 +1,10 @@
 use clap::Parser;
 use clio::Input;
-use rayon::prelude::*;
-use std::io::{self, BufReader, prelude::*};
+use common::output::{Format, report};
+use common::{Part, Solution};
+use day02::{Audit, Day02, Id, Overlaps, Policy, Repeats, Width, audit};
+use std::io::{self, prelude::*};
+use std::process::ExitCode;
 
 #[derive(Parser)]
 struct Opt {
@@ -9,88 +12,79 @@ struct Opt {
     #[clap(value_parser, default_value = "-")]
     input: Input,
 
-    /// the number of CPU cores to use (all if unspecified)
-    #[clap(short, long, default_value_t = num_cpus::get())]
-    threads: usize,
-}
+    /// how to print the answer
+    #[clap(long, value_enum, default_value_t)]
+    format: Format,
 
-fn repeat_digits(digits: usize, repeat_count: usize) -> usize {
-    if digits == 0 {
-        return 0;
-    }
-    let len = digits.ilog10() + 1;
-    let mut ret = 0;
-    for i in 0..repeat_count {
-        let pow = 10usize.pow((i as u32) * len);
-        ret += digits * pow;
-    }
-    ret
-}
+    /// how many times a block of digits has to repeat to make an ID invalid
+    #[clap(long, default_value_t = Policy::REPEATED.repeats)]
+    repeats: Repeats,
 
-fn valid_id(id: usize) -> bool {
-    let len: usize = (id.ilog10() + 1) as usize;
-    for i in 1..=(len / 2) {
-        let slice = id % 10usize.pow(i as u32);
-        if len % i != 0 {
-            continue;
-        }
-        let repeat = repeat_digits(slice, len / i);
-        if id == repeat {
-            return false;
-        }
-    }
-    true
-}
+    /// the fewest digits a repeating block can have
+    #[clap(long, default_value_t = Policy::REPEATED.min_unit)]
+    min_unit: u32,
 
-fn solve(ranges: Vec<&str>) -> usize {
-    let mut ret = 0;
-    for range in ranges {
-        let (start, end) = range.split_once('-').unwrap();
-        let u_start = start.parse::<usize>().unwrap();
-        let u_end = end.parse::<usize>().unwrap();
-        ret += (u_start..=u_end)
-            .into_par_iter()
-            .filter(|id| !valid_id(*id))
-            .sum::<usize>();
-    }
-    ret
-}
+    /// the base the IDs are written in
+    #[clap(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(2..=36))]
+    radix: u32,
 
-fn main() -> io::Result<()> {
-    let opt = Opt::parse();
+    /// how wide an integer to read the IDs into
+    #[clap(long, value_enum, default_value_t)]
+    width: Width,
 
-    rayon::ThreadPoolBuilder::new()
-        .num_threads(opt.threads)
-        .build_global()
-        .unwrap();
+    /// count IDs in several ranges once for each (multiset) or just once (set)
+    #[clap(long, value_enum, default_value_t)]
+    overlaps: Overlaps,
 
-    let mut reader = BufReader::new(opt.input);
-    let mut line = String::new();
-    reader.read_line(&mut line)?;
-    let ranges: Vec<&str> = line.split(',').map(|s| s.trim()).collect();
-    println!("{}", solve(ranges));
-    Ok(())
+    /// list every invalid ID with how it repeats instead of the answer
+    #[clap(long, value_enum)]
+    report: Option<Audit>,
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    #[test]
-    fn given() {
-        let example = Vec::from([
-            "11-22",
-            "95-115",
-            "998-1012",
-            "1188511880-1188511890",
-            "222220-222224",
-            "1698522-1698528",
-            "446443-446449",
-            "38593856-38593862",
-            "565653-565659",
-            "824824821-824824827",
-            "2121212118-2121212124",
-        ]);
-        println!("{}", example.join(","));
-        assert_eq!(solve(example), 4174379265);
+fn main() -> io::Result<ExitCode> {
+    let mut opt = Opt::parse();
+
+    let mut input = String::new();
+    opt.input.read_to_string(&mut input)?;
+    match opt.width {
+        Width::U64 => solve::<u64>(&opt, &input),
+        Width::U128 => solve::<u128>(&opt, &input),
     }
 }
+
+fn solve<T: Id>(opt: &Opt, input: &str) -> io::Result<ExitCode> {
+    let day: Day02<T> = Day02 {
+        part2: Policy {
+            repeats: opt.repeats,
+            min_unit: opt.min_unit,
+        },
+        radix: opt.radix,
+        overlaps: opt.overlaps,
+        ..Day02::default()
+    };
+    let Some(format) = opt.report else {
+        return Ok(report(
+            &day,
+            env!("CARGO_PKG_NAME"),
+            Part::Two,
+            input,
+            opt.format,
+        ));
+    };
+    let ranges = match day.parse(input) {
+        Ok(ranges) => ranges,
+        Err(e) => {
+            eprint!("{}", e.diagnostic(input));
+            return Ok(ExitCode::FAILURE);
+        }
+    };
+    audit(
+        &mut io::stdout().lock(),
+        format,
+        &ranges,
+        day.radix,
+        &day.part2,
+        day.overlaps,
+    )?;
+    Ok(ExitCode::SUCCESS)
+}
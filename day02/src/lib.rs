@@ -0,0 +1,932 @@
+use clap::ValueEnum;
+use common::input;
+use common::parse::{Line, ParseError};
+use common::{Answer, Solution};
+use serde::{Serialize, Serializer};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::io::{self, Write};
+use std::iter::Sum;
+use std::marker::PhantomData;
+use std::num::{IntErrorKind, ParseIntError};
+use std::ops::{Add, AddAssign, Mul, Sub};
+use std::str::FromStr;
+
+/// How many times a block of digits has to repeat for an ID made of it to be invalid
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Repeats {
+    Exactly(u32),
+    AtLeast(u32),
+}
+
+impl fmt::Display for Repeats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Repeats::Exactly(k) => write!(f, "exactly:{}", k),
+            Repeats::AtLeast(k) => write!(f, "at-least:{}", k),
+        }
+    }
+}
+
+impl FromStr for Repeats {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let usage = || format!("'{}' isn't a repeat count like exactly:2 or at-least:2", s);
+        let (kind, count) = s.split_once(':').ok_or_else(usage)?;
+        let count: u32 = count.parse().map_err(|_| usage())?;
+        if count < 2 {
+            return Err(format!(
+                "a block has to repeat at least twice, not {}",
+                count
+            ));
+        }
+        match kind {
+            "exactly" => Ok(Repeats::Exactly(count)),
+            "at-least" => Ok(Repeats::AtLeast(count)),
+            _ => Err(usage()),
+        }
+    }
+}
+
+/// Which IDs count as invalid: those made of a block of at least `min_unit` digits, repeated
+/// as many times as `repeats` asks for
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Policy {
+    pub repeats: Repeats,
+    pub min_unit: u32,
+}
+
+impl Policy {
+    /// Two copies of the same digits, as part 1 asks for
+    pub const HALVES: Policy = Policy {
+        repeats: Repeats::Exactly(2),
+        min_unit: 1,
+    };
+    /// Any number of copies of the same digits, as part 2 asks for
+    pub const REPEATED: Policy = Policy {
+        repeats: Repeats::AtLeast(2),
+        min_unit: 1,
+    };
+
+    /// Whether a `len`-digit ID made of a `unit`-digit block repeated is invalid
+    pub fn allows(&self, unit: u32, len: u32) -> bool {
+        if unit < self.min_unit || unit == 0 || !len.is_multiple_of(unit) {
+            return false;
+        }
+        match self.repeats {
+            Repeats::Exactly(k) => len / unit == k,
+            Repeats::AtLeast(k) => len / unit >= k,
+        }
+    }
+
+    /// Whether a `len`-digit ID whose shortest repeating block is `shortest` digits long is
+    /// invalid, which it is if it can be read as any repeated block the policy allows
+    fn invalid(&self, shortest: u32, len: u32) -> bool {
+        (shortest..len)
+            .step_by(shortest as usize)
+            .any(|unit| self.allows(unit, len))
+    }
+}
+
+/// The integer types IDs can be read into
+///
+/// The arithmetic on them all happens in `u128` whatever the width, with sums of them kept in a
+/// `Total`, which has room for sums of even the largest `u128` IDs.
+pub trait Id: Copy + Ord + fmt::Debug + fmt::Display + Send + Sync + 'static {
+    const BITS: u32;
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseIntError>;
+    fn widen(self) -> u128;
+}
+
+macro_rules! id {
+    ($($t:ty),*) => {
+        $(impl Id for $t {
+            const BITS: u32 = <$t>::BITS;
+
+            fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseIntError> {
+                <$t>::from_str_radix(s, radix)
+            }
+
+            fn widen(self) -> u128 {
+                self as u128
+            }
+        })*
+    };
+}
+
+id!(u64, u128);
+
+/// Which of the `Id` types to read the IDs into
+#[derive(Clone, Copy, Debug, Default, PartialEq, ValueEnum)]
+pub enum Width {
+    #[default]
+    U64,
+    U128,
+}
+
+/// A sum of IDs, 256 bits wide from the least significant 64 up
+///
+/// A range holds fewer than 2^128 IDs, each of them below 2^128, so a range's invalid IDs add
+/// up to less than 2^256, leaving room for the sums of a great many ranges besides.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Total([u64; 4]);
+
+impl From<u128> for Total {
+    fn from(n: u128) -> Self {
+        Total([n as u64, (n >> 64) as u64, 0, 0])
+    }
+}
+
+impl Total {
+    /// The sum as a `u128`, if it fits in one
+    pub fn narrow(&self) -> Option<u128> {
+        let [lo, hi, 0, 0] = self.0 else {
+            return None;
+        };
+        Some((hi as u128) << 64 | lo as u128)
+    }
+}
+
+impl PartialEq<u128> for Total {
+    fn eq(&self, other: &u128) -> bool {
+        *self == Total::from(*other)
+    }
+}
+
+impl PartialEq<Total> for u128 {
+    fn eq(&self, other: &Total) -> bool {
+        Total::from(*self) == *other
+    }
+}
+
+impl Add for Total {
+    type Output = Total;
+
+    fn add(self, rhs: Total) -> Total {
+        let mut ret = [0; 4];
+        let mut carry = 0;
+        for (i, cell) in ret.iter_mut().enumerate() {
+            let sum = self.0[i] as u128 + rhs.0[i] as u128 + carry;
+            *cell = sum as u64;
+            carry = sum >> 64;
+        }
+        Total(ret)
+    }
+}
+
+impl AddAssign for Total {
+    fn add_assign(&mut self, rhs: Total) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Total {
+    type Output = Total;
+
+    fn sub(self, rhs: Total) -> Total {
+        let mut ret = [0; 4];
+        let mut borrow = false;
+        for (i, cell) in ret.iter_mut().enumerate() {
+            let (diff, under) = self.0[i].overflowing_sub(rhs.0[i]);
+            let (diff, under_again) = diff.overflowing_sub(borrow as u64);
+            *cell = diff;
+            borrow = under || under_again;
+        }
+        Total(ret)
+    }
+}
+
+impl Mul<u128> for Total {
+    type Output = Total;
+
+    /// Long multiplication a 64-bit digit at a time, dropping anything past 256 bits
+    fn mul(self, rhs: u128) -> Total {
+        let mut ret = [0; 4];
+        for (j, digit) in [rhs as u64, (rhs >> 64) as u64].into_iter().enumerate() {
+            let mut carry = 0;
+            for i in 0..4 - j {
+                let cell = ret[i + j] as u128 + self.0[i] as u128 * digit as u128 + carry;
+                ret[i + j] = cell as u64;
+                carry = cell >> 64;
+            }
+        }
+        Total(ret)
+    }
+}
+
+impl Sum for Total {
+    fn sum<I: Iterator<Item = Total>>(iter: I) -> Total {
+        iter.fold(Total::default(), Add::add)
+    }
+}
+
+impl fmt::Display for Total {
+    /// In decimal, taking off 19 digits at a time as that's the most a `u64` can hold
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const CHUNK: u128 = 10u128.pow(19);
+        let mut rest = self.0;
+        let mut chunks = Vec::new();
+        loop {
+            let mut remainder = 0;
+            for cell in rest.iter_mut().rev() {
+                let n = remainder << 64 | *cell as u128;
+                *cell = (n / CHUNK) as u64;
+                remainder = n % CHUNK;
+            }
+            chunks.push(remainder);
+            if rest == [0; 4] {
+                break;
+            }
+        }
+        let mut digits = chunks.pop().unwrap().to_string();
+        for chunk in chunks.into_iter().rev() {
+            digits.push_str(&format!("{:019}", chunk));
+        }
+        f.pad(&digits)
+    }
+}
+
+impl Answer for Total {
+    fn render(&self) -> Result<String, ParseError> {
+        Ok(self.to_string())
+    }
+}
+
+impl Serialize for Total {
+    /// As a number when it fits in a `u128`, the widest serde has, and as a string past that
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.narrow() {
+            Some(n) => serializer.serialize_u128(n),
+            None => serializer.collect_str(self),
+        }
+    }
+}
+
+pub struct Day02<T = u64> {
+    pub part1: Policy,
+    pub part2: Policy,
+    /// the base the IDs are written in, from 2 to 36
+    pub radix: u32,
+    /// whether an ID in several ranges counts once for each of them
+    pub overlaps: Overlaps,
+    /// the `Id` type the IDs are read into
+    pub id: PhantomData<T>,
+}
+
+impl<T> Default for Day02<T> {
+    fn default() -> Self {
+        Self {
+            part1: Policy::HALVES,
+            part2: Policy::REPEATED,
+            radix: 10,
+            overlaps: Overlaps::default(),
+            id: PhantomData,
+        }
+    }
+}
+
+pub type Ranges<T = u64> = Vec<(T, T)>;
+
+/// How to count IDs that fall in more than one range
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum Overlaps {
+    /// once for every range holding them
+    Multiset,
+    /// just once, by merging overlapping ranges first
+    #[default]
+    Set,
+}
+
+/// Sort the ranges and merge those that share any IDs, so no ID is in more than one of them
+pub fn merge<T: Id>(ranges: &Ranges<T>) -> Ranges<T> {
+    let mut sorted = ranges.clone();
+    sorted.sort();
+    let mut merged: Ranges<T> = Vec::with_capacity(sorted.len());
+    for (start, end) in sorted {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+/// Counting digits and repeating blocks of them in a particular base
+#[derive(Clone, Copy)]
+struct Base(u128);
+
+impl Base {
+    /// How many digits `id` has
+    fn digits(&self, id: u128) -> u32 {
+        id.checked_ilog(self.0).unwrap_or(0) + 1
+    }
+
+    /// The smallest number with `len` digits
+    fn first(&self, len: u32) -> u128 {
+        self.0.pow(len - 1)
+    }
+
+    /// The largest number with `len` digits
+    fn last(&self, len: u32) -> u128 {
+        self.0.checked_pow(len).map_or(u128::MAX, |p| p - 1)
+    }
+
+    /// What to multiply a `unit`-digit number by to repeat it `count` times, 1001 for 2 and 2
+    /// in decimal, or None if that's too big for any ID
+    fn repeater(&self, unit: u32, count: u32) -> Option<u128> {
+        (0..count).try_fold(0u128, |ret, i| {
+            ret.checked_add(self.0.checked_pow(i * unit)?)
+        })
+    }
+
+    /// The sum of the `len`-digit IDs in `start..=end` made of a `unit`-digit block repeated
+    ///
+    /// Those IDs are the block times a fixed repeater, so the blocks that land in the range
+    /// form a run of consecutive numbers and their sum is an arithmetic series.
+    fn repeated_sum(&self, start: u128, end: u128, len: u32, unit: u32) -> Total {
+        let Some(repeater) = self.repeater(unit, len / unit) else {
+            return Total::default();
+        };
+        let lo = start.div_ceil(repeater).max(self.first(unit));
+        let hi = (end / repeater).min(self.last(unit));
+        if lo > hi {
+            return Total::default();
+        }
+        // one of the count and the sum of the ends is even, halve that one
+        let count = hi - lo + 1;
+        let series = if count.is_multiple_of(2) {
+            Total::from(count / 2) * (lo + hi)
+        } else {
+            Total::from(count) * ((lo + hi) / 2)
+        };
+        series * repeater
+    }
+
+    /// The sum of the `len`-digit IDs in `start..=end` that repeat a shorter block of digits,
+    /// counting only those whose shortest repeating block has a length `invalid` accepts
+    ///
+    /// An ID repeating a block of `d` digits also repeats every block whose length is a
+    /// multiple of `d`, so summing `repeated_sum` over block lengths would count IDs several
+    /// times. Instead this works up through the block lengths dividing `len`, taking off
+    /// everything already counted for the shorter blocks dividing each one, leaving the IDs
+    /// whose shortest block is exactly that long.
+    fn invalid_sum(
+        &self,
+        start: u128,
+        end: u128,
+        len: u32,
+        invalid: impl Fn(u32) -> bool,
+    ) -> Total {
+        let units: Vec<u32> = (1..len).filter(|u| len.is_multiple_of(*u)).collect();
+        let mut shortest: Vec<Total> = Vec::with_capacity(units.len());
+        for (i, &unit) in units.iter().enumerate() {
+            let shorter: Total = units[..i]
+                .iter()
+                .zip(shortest.iter())
+                .filter(|(u, _)| unit.is_multiple_of(**u))
+                .map(|(_, sum)| *sum)
+                .sum();
+            shortest.push(self.repeated_sum(start, end, len, unit) - shorter);
+        }
+        units
+            .into_iter()
+            .zip(shortest)
+            .filter(|(unit, _)| invalid(*unit))
+            .map(|(_, sum)| sum)
+            .sum()
+    }
+
+    /// The sum of the IDs in a range that `policy` says are invalid, one digit count at a time
+    /// so each piece only holds IDs of a single length
+    fn range_sum(&self, start: u128, end: u128, policy: &Policy) -> Total {
+        (self.digits(start)..=self.digits(end))
+            .map(|len| {
+                let lo = start.max(self.first(len));
+                let hi = end.min(self.last(len));
+                self.invalid_sum(lo, hi, len, |unit| policy.invalid(unit, len))
+            })
+            .sum()
+    }
+
+    /// Write `id` out the way it would appear in the input
+    fn format(&self, mut id: u128) -> String {
+        let mut digits = Vec::new();
+        loop {
+            digits.push(char::from_digit((id % self.0) as u32, self.0 as u32).unwrap());
+            id /= self.0;
+            if id == 0 {
+                break;
+            }
+        }
+        digits.into_iter().rev().collect()
+    }
+
+    /// Whether a `unit`-digit block is itself some shorter block repeated
+    fn periodic(&self, block: u128, unit: u32) -> bool {
+        (1..unit)
+            .filter(|d| unit.is_multiple_of(*d))
+            .filter_map(|d| self.repeater(d, unit / d))
+            .any(|repeater| block.is_multiple_of(repeater))
+    }
+
+    /// Every ID in `start..=end` that `policy` says is invalid, in order
+    ///
+    /// Rather than checking each ID, this goes through the blocks that can't be split any
+    /// further, so each invalid ID is made exactly once, from its shortest repeating block.
+    fn invalid_ids(&self, start: u128, end: u128, policy: &Policy) -> Vec<Invalid> {
+        let mut found = Vec::new();
+        for len in self.digits(start)..=self.digits(end) {
+            let lo = start.max(self.first(len));
+            let hi = end.min(self.last(len));
+            for shortest in (1..len).filter(|u| len.is_multiple_of(*u)) {
+                // the unit the policy reads the ID as, if it reads it as one at all
+                let Some(unit) = (shortest..len)
+                    .step_by(shortest as usize)
+                    .find(|&unit| policy.allows(unit, len))
+                else {
+                    continue;
+                };
+                let Some(repeater) = self.repeater(shortest, len / shortest) else {
+                    continue;
+                };
+                let first = lo.div_ceil(repeater).max(self.first(shortest));
+                let last = (hi / repeater).min(self.last(shortest));
+                for block in first..=last {
+                    if self.periodic(block, shortest) {
+                        continue;
+                    }
+                    let id = block * repeater;
+                    found.push(Invalid {
+                        id,
+                        range: (start, end),
+                        unit: id / self.0.pow(len - unit),
+                        unit_len: unit,
+                        repeats: len / unit,
+                    });
+                }
+            }
+        }
+        found.sort_by_key(|invalid| invalid.id);
+        found
+    }
+}
+
+/// The sum of every invalid ID across the ranges, written in base `radix`
+pub fn total<T: Id>(ranges: &Ranges<T>, radix: u32, policy: &Policy) -> Total {
+    let base = Base(radix as u128);
+    ranges
+        .iter()
+        .map(|&(start, end)| base.range_sum(start.widen(), end.widen(), policy))
+        .sum()
+}
+
+/// An invalid ID, along with where it came from and how it repeats
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Invalid {
+    pub id: u128,
+    /// the range in the input that held it
+    pub range: (u128, u128),
+    /// the block of digits that repeats, the shortest one the policy accepts
+    pub unit: u128,
+    /// how many digits are in the block
+    pub unit_len: u32,
+    /// how many times the block repeats
+    pub repeats: u32,
+}
+
+/// Every invalid ID in the ranges, range by range, with IDs in more than one range repeated
+/// unless they've been merged first
+pub fn invalid_ids<T: Id>(ranges: &Ranges<T>, radix: u32, policy: &Policy) -> Vec<Invalid> {
+    let base = Base(radix as u128);
+    ranges
+        .iter()
+        .flat_map(|&(start, end)| base.invalid_ids(start.widen(), end.widen(), policy))
+        .collect()
+}
+
+/// How many invalid IDs repeat blocks of one length, and what they add up to
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct UnitStats {
+    pub unit_len: u32,
+    pub ids: usize,
+    pub sum: Total,
+}
+
+/// The invalid IDs grouped by the length of their repeating block, shortest first
+pub fn unit_stats(invalid: &[Invalid]) -> Vec<UnitStats> {
+    let mut stats: BTreeMap<u32, UnitStats> = BTreeMap::new();
+    for found in invalid {
+        let entry = stats.entry(found.unit_len).or_insert(UnitStats {
+            unit_len: found.unit_len,
+            ..UnitStats::default()
+        });
+        entry.ids += 1;
+        entry.sum += found.id.into();
+    }
+    stats.into_values().collect()
+}
+
+/// Formats the invalid ID report can be written in
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum Audit {
+    Csv,
+    Json,
+}
+
+/// A line of the report, with the IDs written as they are in the input
+#[derive(Serialize)]
+struct Row {
+    range: String,
+    id: String,
+    unit: String,
+    unit_len: u32,
+    repeats: u32,
+}
+
+/// Write out every invalid ID in the ranges followed by the statistics for each block length
+///
+/// IDs are written in base `radix` as in the input, while the sums are in decimal like the
+/// answers. The CSV has the IDs and the statistics as two tables separated by a blank line.
+/// Each ID is listed against the range in the input it came from, and with `Overlaps::Set`
+/// only against the first of those, as that's all the answer counts it for.
+pub fn audit<T: Id>(
+    out: &mut impl Write,
+    format: Audit,
+    ranges: &Ranges<T>,
+    radix: u32,
+    policy: &Policy,
+    overlaps: Overlaps,
+) -> io::Result<()> {
+    let base = Base(radix as u128);
+    let mut invalid = invalid_ids(ranges, radix, policy);
+    if overlaps == Overlaps::Set {
+        let mut seen = HashSet::new();
+        invalid.retain(|found| seen.insert(found.id));
+    }
+    let stats = unit_stats(&invalid);
+    let rows = invalid.iter().map(|found| Row {
+        range: format!(
+            "{}-{}",
+            base.format(found.range.0),
+            base.format(found.range.1)
+        ),
+        id: base.format(found.id),
+        unit: base.format(found.unit),
+        unit_len: found.unit_len,
+        repeats: found.repeats,
+    });
+    match format {
+        Audit::Csv => {
+            writeln!(out, "range,id,unit,unit_len,repeats")?;
+            for row in rows {
+                writeln!(
+                    out,
+                    "{},{},{},{},{}",
+                    row.range, row.id, row.unit, row.unit_len, row.repeats
+                )?;
+            }
+            writeln!(out)?;
+            writeln!(out, "unit_len,ids,sum")?;
+            for unit in stats {
+                writeln!(out, "{},{},{}", unit.unit_len, unit.ids, unit.sum)?;
+            }
+        }
+        Audit::Json => {
+            let report = serde_json::json!({
+                "ids": rows.collect::<Vec<Row>>(),
+                "units": stats,
+            });
+            writeln!(out, "{}", report)?;
+        }
+    }
+    Ok(())
+}
+
+impl<T: Id> Day02<T> {
+    fn id(&self, line: &Line, field: &str) -> Result<T, ParseError> {
+        T::from_str_radix(field, self.radix).map_err(|e| {
+            let expected = match e.kind() {
+                IntErrorKind::PosOverflow => format!("an ID that fits in {} bits", T::BITS),
+                _ => format!("a base {} number", self.radix),
+            };
+            line.error(field, expected)
+        })
+    }
+
+    /// The ranges to look for invalid IDs in, merged unless overlaps are to be counted twice
+    pub fn normalize(&self, ranges: &Ranges<T>) -> Ranges<T> {
+        match self.overlaps {
+            Overlaps::Multiset => ranges.clone(),
+            Overlaps::Set => merge(ranges),
+        }
+    }
+}
+
+impl<T: Id> Solution for Day02<T> {
+    type Parsed = Ranges<T>;
+    type Part1 = Total;
+    type Part2 = Total;
+
+    fn parse(&self, input: &str) -> Result<Ranges<T>, ParseError> {
+        let mut ranges = Vec::new();
+        for line in input::lines(input) {
+            let fields = line
+                .text
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|range| !range.is_empty());
+            for range in fields {
+                let (start, end) = range
+                    .split_once('-')
+                    .ok_or_else(|| line.error(range, "a range like '11-22'"))?;
+                ranges.push((self.id(&line, start)?, self.id(&line, end)?));
+            }
+        }
+        if ranges.is_empty() {
+            return Err(ParseError::end(input, "a list of ranges"));
+        }
+        Ok(ranges)
+    }
+
+    fn part1(&self, ranges: &Ranges<T>) -> Total {
+        total(&self.normalize(ranges), self.radix, &self.part1)
+    }
+
+    fn part2(&self, ranges: &Ranges<T>) -> Total {
+        total(&self.normalize(ranges), self.radix, &self.part2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: [&str; 11] = [
+        "11-22",
+        "95-115",
+        "998-1012",
+        "1188511880-1188511890",
+        "222220-222224",
+        "1698522-1698528",
+        "446443-446449",
+        "38593856-38593862",
+        "565653-565659",
+        "824824821-824824827",
+        "2121212118-2121212124",
+    ];
+
+    #[test]
+    fn part1_given() {
+        println!("{}", EXAMPLE.join(","));
+        assert_eq!(
+            <Day02>::default().part1(&<Day02>::default().parse(&EXAMPLE.join(",")).unwrap()),
+            1227775554
+        );
+    }
+
+    #[test]
+    fn part2_given() {
+        println!("{}", EXAMPLE.join(","));
+        assert_eq!(
+            <Day02>::default().part2(&<Day02>::default().parse(&EXAMPLE.join(",")).unwrap()),
+            4174379265
+        );
+    }
+
+    /// The old way of finding invalid IDs, checking every ID in turn
+    fn brute_force(start: u128, end: u128, radix: u32, policy: &Policy) -> u128 {
+        (start..=end)
+            .filter(|&id| {
+                let id = Base(radix as u128).format(id);
+                let len = id.len();
+                (1..len)
+                    .filter(|&unit| policy.allows(unit as u32, len as u32))
+                    .any(|unit| id == id[..unit].repeat(len / unit))
+            })
+            .sum()
+    }
+
+    #[test]
+    fn closed_form() {
+        for (start, end) in [(1, 100_000), (95, 115), (998, 1012), (123_456, 1_234_567)] {
+            let ranges = vec![(start, end)];
+            assert_eq!(
+                <Day02>::default().part1(&ranges),
+                brute_force(start as u128, end as u128, 10, &Policy::HALVES)
+            );
+            assert_eq!(
+                <Day02>::default().part2(&ranges),
+                brute_force(start as u128, end as u128, 10, &Policy::REPEATED)
+            );
+        }
+        // far too wide to check ID by ID, only 100000000100000000 to 100000009100000009 repeat
+        let wide = vec![(10u64.pow(17), 10u64.pow(17) + 10u64.pow(10))];
+        assert_eq!(<Day02>::default().part1(&wide), 1000000046000000045);
+        assert_eq!(<Day02>::default().part2(&wide), 1000000046000000045);
+        assert_eq!(<Day02>::default().part1(&vec![(11, 11), (0, 0)]), 11);
+    }
+
+    #[test]
+    fn policies() {
+        let policies = ["exactly:3", "at-least:3", "exactly:2", "at-least:4"]
+            .map(|repeats| repeats.parse::<Repeats>().unwrap());
+        for repeats in policies {
+            for min_unit in 1..=3 {
+                let policy = Policy { repeats, min_unit };
+                assert_eq!(
+                    total(&vec![(1u64, 234_567)], 10, &policy),
+                    brute_force(1, 234_567, 10, &policy),
+                    "{} {}",
+                    repeats,
+                    min_unit
+                );
+                let invalid = invalid_ids(&vec![(1u64, 234_567)], 10, &policy);
+                assert_eq!(
+                    invalid.iter().map(|found| found.id).sum::<u128>(),
+                    total(&vec![(1u64, 234_567)], 10, &policy)
+                );
+            }
+        }
+        assert!("exactly:1".parse::<Repeats>().is_err());
+        assert!("twice".parse::<Repeats>().is_err());
+    }
+
+    #[test]
+    fn radixes() {
+        for radix in [2, 3, 7, 16, 36] {
+            let day = Day02 {
+                radix,
+                ..<Day02>::default()
+            };
+            let (start, end) = (1, 70_000);
+            let input = format!(
+                "{}-{}",
+                Base(radix as u128).format(start),
+                Base(radix as u128).format(end)
+            );
+            let ranges = day.parse(&input).unwrap();
+            assert_eq!(ranges, vec![(1, 70_000)]);
+            assert_eq!(
+                day.part1(&ranges),
+                brute_force(start, end, radix, &Policy::HALVES),
+                "base {}",
+                radix
+            );
+            assert_eq!(
+                day.part2(&ranges),
+                brute_force(start, end, radix, &Policy::REPEATED),
+                "base {}",
+                radix
+            );
+        }
+        let hex = Day02 {
+            radix: 16,
+            ..<Day02>::default()
+        };
+        assert_eq!(
+            hex.part1(&hex.parse("a-ff,ABAB-abab").unwrap()),
+            (1..=15).map(|d| d * 0x11).sum::<u128>() + 0xabab
+        );
+        let error = hex.parse("10-1g").unwrap_err();
+        assert_eq!(
+            (error.column, error.expected.as_str()),
+            (4, "a base 16 number")
+        );
+    }
+
+    #[test]
+    fn widths() {
+        // 20 and 21 digit IDs, past what fits in a u64
+        let input = "99999999999999999999-100000000010000000000";
+        let error = <Day02>::default().parse(input).unwrap_err();
+        assert_eq!(error.expected, "an ID that fits in 64 bits");
+
+        let day: Day02<u128> = Day02::default();
+        let ranges = day.parse(input).unwrap();
+        assert_eq!(day.part1(&ranges), 99999999999999999999);
+        assert_eq!(day.part2(&ranges), 99999999999999999999);
+        // only 10^12 written out twice repeats in here
+        let wide = vec![(10u128.pow(25), 10u128.pow(25) + 10u128.pow(13))];
+        assert_eq!(day.part1(&wide), 10u128.pow(25) + 10u128.pow(12));
+        assert_eq!(day.part2(&wide), 10u128.pow(25) + 10u128.pow(12));
+    }
+
+    #[test]
+    fn wide_totals() {
+        let max = Total::from(u128::MAX);
+        assert_eq!(
+            (max + Total::from(1)).to_string(),
+            "340282366920938463463374607431768211456"
+        );
+        assert_eq!(max + Total::from(1) - Total::from(1), u128::MAX);
+        assert_eq!(max.narrow(), Some(u128::MAX));
+        assert_eq!((max * u128::MAX).narrow(), None);
+        assert_eq!(
+            (max * u128::MAX).to_string(),
+            "115792089237316195423570985008687907852589419931798687112530834793049593217025"
+        );
+
+        // every 128 bit ID made of a 64 bit block twice over, adding up to far more than 2^128
+        let day = Day02::<u128> {
+            radix: 2,
+            ..Day02::default()
+        };
+        let input = format!("1{}-{}", "0".repeat(127), "1".repeat(128));
+        let ranges = day.parse(&input).unwrap();
+        let blocks = Total::from(1 << 62) * ((1 << 63) + u64::MAX as u128);
+        assert_eq!(day.part1(&ranges), blocks * ((1 << 64) + 1));
+        assert_eq!(
+            serde_json::to_string(&unit_stats(&invalid_ids(
+                &vec![(3u128, 3)],
+                2,
+                &Policy::HALVES
+            )))
+            .unwrap(),
+            r#"[{"unit_len":1,"ids":1,"sum":3}]"#
+        );
+    }
+
+    #[test]
+    fn report() {
+        let day = <Day02>::default();
+        let ranges = day.parse(&EXAMPLE.join(",")).unwrap();
+        for policy in [Policy::HALVES, Policy::REPEATED] {
+            let invalid = invalid_ids(&ranges, 10, &policy);
+            assert_eq!(
+                invalid.iter().map(|found| found.id).sum::<u128>(),
+                total(&ranges, 10, &policy)
+            );
+            let stats = unit_stats(&invalid);
+            assert_eq!(
+                stats.iter().map(|unit| unit.ids).sum::<usize>(),
+                invalid.len()
+            );
+        }
+
+        let invalid = invalid_ids(&vec![(1000u64, 1111), (2222, 2222)], 10, &Policy::REPEATED);
+        let found: Vec<(u128, u128, u32, u32)> = invalid
+            .iter()
+            .map(|found| (found.id, found.unit, found.unit_len, found.repeats))
+            .collect();
+        assert_eq!(
+            found,
+            vec![(1010, 10, 2, 2), (1111, 1, 1, 4), (2222, 2, 1, 4)]
+        );
+
+        let audited = |ranges: &Ranges<u64>, overlaps| {
+            let mut out = Vec::new();
+            audit(&mut out, Audit::Csv, ranges, 10, &Policy::HALVES, overlaps).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        assert_eq!(
+            audited(&vec![(1000, 1111)], Overlaps::Set),
+            "range,id,unit,unit_len,repeats\n\
+             1000-1111,1010,10,2,2\n\
+             1000-1111,1111,11,2,2\n\
+             \n\
+             unit_len,ids,sum\n\
+             2,2,2121\n"
+        );
+        // the ranges stay as they were in the input, with 1111 only counted for the first
+        let overlapping = vec![(1100, 1212), (1000, 1111)];
+        assert_eq!(
+            audited(&overlapping, Overlaps::Set),
+            "range,id,unit,unit_len,repeats\n\
+             1100-1212,1111,11,2,2\n\
+             1100-1212,1212,12,2,2\n\
+             1000-1111,1010,10,2,2\n\
+             \n\
+             unit_len,ids,sum\n\
+             2,3,3333\n"
+        );
+        assert!(audited(&overlapping, Overlaps::Multiset).contains("1000-1111,1111,11,2,2\n"));
+    }
+
+    #[test]
+    fn overlaps() {
+        let day = <Day02>::default();
+        let ranges = day.parse("95-115, 11-22,\n100-120 5-12,\n\n").unwrap();
+        assert_eq!(ranges, vec![(95, 115), (11, 22), (100, 120), (5, 12)]);
+        assert_eq!(merge(&ranges), vec![(5, 22), (95, 120)]);
+        assert_eq!(day.part2(&ranges), 11 + 22 + 99 + 111);
+
+        let multiset = Day02 {
+            overlaps: Overlaps::Multiset,
+            ..<Day02>::default()
+        };
+        assert_eq!(multiset.part2(&ranges), 11 + 22 + 99 + 111 + 111 + 11);
+        assert_eq!(
+            day.part1(&day.parse(&EXAMPLE.join("\n")).unwrap()),
+            1227775554
+        );
+
+        let error = day.parse("11-22,\n95+115\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(day.parse(" ,\n").unwrap_err().expected, "a list of ranges");
+    }
+
+    #[test]
+    fn answers() {
+        common::answers::verify(&<Day02>::default(), env!("CARGO_MANIFEST_DIR"));
+    }
+}
@@ -1,7 +1,10 @@
 use clap::Parser;
 use clio::Input;
-use rayon::prelude::*;
-use std::io::{self, BufReader, prelude::*};
+use common::output::{Format, report};
+use common::{Part, Solution};
+use day03::{Constraints, Day03, Highlight, Window, explain};
+use std::io::{self, IsTerminal, prelude::*};
+use std::process::ExitCode;
 
 #[derive(Parser)]
 struct Opt {
@@ -9,115 +12,65 @@ struct Opt {
     #[clap(value_parser, default_value = "-")]
     input: Input,
 
-    /// the number of CPU cores to use (all if unspecified)
-    #[clap(short, long, default_value_t = num_cpus::get())]
-    threads: usize,
-}
-
-struct Bank {
-    batteries: Vec<usize>,
-}
+    /// how to print the answer
+    #[clap(long, value_enum, default_value_t)]
+    format: Format,
 
-impl Bank {
-    fn new() -> Self {
-        Self {
-            batteries: Vec::new(),
-        }
-    }
-    fn add(&mut self, battery: usize) {
-        self.batteries.push(battery);
-    }
+    /// how many batteries to turn on in each bank
+    #[clap(short, long, default_value_t = Day03::default().part1)]
+    batteries: usize,
 
-    fn highest(&self) -> usize {
-        let highest = self._highest(None, 9);
-        let next_highest = self._highest(Some(highest.0), 9);
-        if next_highest.1 == 0 {
-            let second_highest = self._highest(None, highest.1 - 1);
-            let pair = second_highest.1 * 10 + highest.1;
-            //eprintln!("Found (second candidate) highest pair: {}", pair);
-            return pair;
-        }
-        let pair = highest.1 * 10 + next_highest.1;
-        //eprintln!("Found highest pair: {}", pair);
-        pair
-    }
-    fn _highest(&self, other: Option<usize>, max_digit: usize) -> (usize, usize) {
-        let starting_point = other.unwrap_or_else(|| 0);
-        let mut ret: (usize, usize) = (0, 0);
-        for (i, battery) in self.batteries.iter().enumerate() {
-            if i <= starting_point && other.is_some() {
-                continue;
-            }
-            if *battery == max_digit {
-                return (i, *battery);
-            }
-            if *battery > ret.1 && *battery <= max_digit {
-                ret = (i, *battery);
-            }
-        }
-        ret
-    }
-}
+    /// how far on each battery turned on has to be from the one before
+    #[clap(long, default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+    gap: u64,
 
-struct PowerSupply {
-    banks: Vec<Bank>,
-}
+    /// at most this many batteries on in any run of so many, like 2/5
+    #[clap(long)]
+    window: Option<Window>,
 
-impl PowerSupply {
-    fn new() -> Self {
-        Self { banks: Vec::new() }
-    }
-    fn add(&mut self, bank: Bank) {
-        self.banks.push(bank);
-    }
-}
+    /// show the batteries chosen in each bank, and the joltage they make, on stderr
+    #[clap(long)]
+    explain: bool,
 
-fn solve(lines: Vec<String>) -> usize {
-    let mut supply = PowerSupply::new();
-    for line in lines {
-        let mut bank = Bank::new();
-        for char in line.chars() {
-            let maybe_digit = char.to_digit(10);
-            if let Some(digit) = maybe_digit {
-                bank.add(digit as usize)
-            }
-        }
-        supply.add(bank);
-    }
-    supply.banks.par_iter().map(|b| b.highest()).sum()
+    /// the number of CPU cores to use (all if unspecified)
+    #[clap(short, long, default_value_t = num_cpus::get())]
+    threads: usize,
 }
 
-fn main() -> io::Result<()> {
-    let opt = Opt::parse();
+fn main() -> io::Result<ExitCode> {
+    let mut opt = Opt::parse();
 
     rayon::ThreadPoolBuilder::new()
         .num_threads(opt.threads)
         .build_global()
         .unwrap();
 
-    let reader = BufReader::new(opt.input);
-    let lines: Vec<String> = reader.lines().flatten().filter(|s| !s.is_empty()).collect();
-    println!("{}", solve(lines));
-    Ok(())
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    fn convert_example(example: &[&str]) -> Vec<String> {
-        example.iter().map(|line| line.to_string()).collect()
-    }
-
-    #[test]
-    fn given() {
-        let example = [
-            "987654321111111",
-            "811111111111119",
-            "234234234234278",
-            "818181911112111",
-        ];
-        println!("{}", example.join("\n"));
-        assert_eq!(solve(convert_example(&example)), 357);
+    let mut input = String::new();
+    opt.input.read_to_string(&mut input)?;
+    let day = Day03 {
+        part1: opt.batteries,
+        constraints: Constraints {
+            gap: opt.gap as usize,
+            window: opt.window,
+        },
+        ..Day03::default()
+    };
+    if opt.explain
+        && let Ok(supply) = day.parse(&input)
+    {
+        let mut err = io::stderr().lock();
+        let highlight = if err.is_terminal() {
+            Highlight::Ansi
+        } else {
+            Highlight::Brackets
+        };
+        explain(&mut err, &supply, day.part1, &day.constraints, highlight)?;
     }
+    Ok(report(
+        &day,
+        env!("CARGO_PKG_NAME"),
+        Part::One,
+        &input,
+        opt.format,
+    ))
 }
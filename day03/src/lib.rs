@@ -0,0 +1,705 @@
+use common::input;
+use common::parse::ParseError;
+use common::{Answer, Solution};
+use rayon::prelude::*;
+use std::fmt;
+use std::io::{self, Write};
+use std::iter::Sum;
+use std::ops::{Add, AddAssign};
+use std::str::FromStr;
+
+/// How many batteries each part turns on in every bank, and which can be on together
+///
+/// Parsing takes banks of any length, since one too short for a part is only a problem for
+/// that part, which reports it as an error pointing at the bank.
+pub struct Day03 {
+    /// how many batteries part 1 turns on in each bank
+    pub part1: usize,
+    /// how many batteries part 2 turns on in each bank
+    pub part2: usize,
+    /// which batteries can be turned on together
+    pub constraints: Constraints,
+}
+
+impl Default for Day03 {
+    fn default() -> Self {
+        Self {
+            part1: 2,
+            part2: 12,
+            constraints: Constraints::NONE,
+        }
+    }
+}
+
+/// A joltage of any number of digits, so any number of batteries can be turned on and added up
+/// without overflowing
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Joltage {
+    /// decimal digits, least significant first, with no zeros at the top
+    digits: Vec<u8>,
+}
+
+impl Joltage {
+    /// The joltage with these digits, most significant first
+    pub fn from_digits(digits: impl DoubleEndedIterator<Item = u8>) -> Self {
+        let mut joltage = Self {
+            digits: digits.rev().collect(),
+        };
+        joltage.trim();
+        joltage
+    }
+
+    fn trim(&mut self) {
+        while self.digits.last() == Some(&0) {
+            self.digits.pop();
+        }
+    }
+
+    /// Add on the number with these digits, least significant first, without building it
+    fn add_digits(&mut self, digits: impl ExactSizeIterator<Item = u8>) {
+        if digits.len() > self.digits.len() {
+            self.digits.resize(digits.len(), 0);
+        }
+        let len = digits.len();
+        let mut carry = 0;
+        for (digit, other) in self.digits.iter_mut().zip(digits) {
+            let sum = *digit + other + carry;
+            *digit = sum % 10;
+            carry = sum / 10;
+        }
+        for digit in self.digits[len..].iter_mut() {
+            if carry == 0 {
+                break;
+            }
+            let sum = *digit + carry;
+            *digit = sum % 10;
+            carry = sum / 10;
+        }
+        if carry > 0 {
+            self.digits.push(carry);
+        }
+        self.trim();
+    }
+}
+
+impl From<u128> for Joltage {
+    fn from(mut value: u128) -> Self {
+        let mut digits = Vec::new();
+        while value > 0 {
+            digits.push((value % 10) as u8);
+            value /= 10;
+        }
+        Self { digits }
+    }
+}
+
+impl AddAssign<&Joltage> for Joltage {
+    fn add_assign(&mut self, other: &Joltage) {
+        self.add_digits(other.digits.iter().copied());
+    }
+}
+
+impl Add for Joltage {
+    type Output = Joltage;
+
+    fn add(mut self, other: Joltage) -> Joltage {
+        self += &other;
+        self
+    }
+}
+
+impl Sum for Joltage {
+    fn sum<I: Iterator<Item = Joltage>>(iter: I) -> Joltage {
+        iter.fold(Joltage::default(), Add::add)
+    }
+}
+
+impl Answer for Joltage {
+    fn render(&self) -> Result<String, ParseError> {
+        Ok(self.to_string())
+    }
+}
+
+impl fmt::Display for Joltage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.digits.is_empty() {
+            return write!(f, "0");
+        }
+        for digit in self.digits.iter().rev() {
+            write!(f, "{}", digit)?;
+        }
+        Ok(())
+    }
+}
+
+/// At most `most` chosen batteries in any `width` batteries in a row
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Window {
+    pub most: usize,
+    pub width: usize,
+}
+
+impl fmt::Display for Window {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.most, self.width)
+    }
+}
+
+impl FromStr for Window {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let usage = || format!("'{}' isn't a window like 2/5, at most 2 in any 5", s);
+        let (most, width) = s.split_once('/').ok_or_else(usage)?;
+        let most: usize = most.parse().map_err(|_| usage())?;
+        let width: usize = width.parse().map_err(|_| usage())?;
+        if most == 0 || width == 0 {
+            return Err(format!("a window has to allow some batteries, not {}", s));
+        }
+        Ok(Window { most, width })
+    }
+}
+
+/// Rules on which batteries can be turned on together
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Constraints {
+    /// how far on each chosen battery is from the one before, 1 allowing neighbours
+    pub gap: usize,
+    pub window: Option<Window>,
+}
+
+impl Constraints {
+    /// Any batteries at all, as the puzzle has it
+    pub const NONE: Constraints = Constraints {
+        gap: 1,
+        window: None,
+    };
+
+    /// Whether these rule nothing out
+    pub fn is_none(&self) -> bool {
+        self.gap <= 1 && self.window.is_none_or(|window| window.most >= window.width)
+    }
+}
+
+impl fmt::Display for Constraints {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "at least {} apart", self.gap)?;
+        if let Some(window) = self.window {
+            write!(f, ", at most {} in any {}", window.most, window.width)?;
+        }
+        Ok(())
+    }
+}
+
+pub struct Bank {
+    batteries: Vec<usize>,
+    /// the line of the input it was on
+    line: usize,
+}
+
+impl Bank {
+    fn new(line: usize) -> Self {
+        Self {
+            batteries: Vec::new(),
+            line,
+        }
+    }
+    fn add(&mut self, battery: usize) {
+        self.batteries.push(battery);
+    }
+
+    /// The positions of the `k` batteries making the highest joltage, in order, or None if
+    /// there aren't that many
+    ///
+    /// Each battery is the highest, and then earliest, one after the last one chosen that
+    /// still leaves enough batteries for the rest. Finding it stops at the first 9, so on a
+    /// bank of mixed digits each one is a short scan. When the scans could add up to more than
+    /// going along the whole bank a few times over, this uses `select_stacked` instead.
+    fn select(&self, k: usize) -> Option<Vec<usize>> {
+        let droppable = self.batteries.len().checked_sub(k)?;
+        if k.saturating_mul(droppable) > Self::SCAN_LIMIT * self.batteries.len() {
+            return self.select_stacked(k);
+        }
+        let mut chosen = Vec::with_capacity(k);
+        let mut from = 0;
+        for last in droppable..self.batteries.len() {
+            let mut best = from;
+            for i in from..=last {
+                if self.batteries[i] > self.batteries[best] {
+                    best = i;
+                    if self.batteries[i] == 9 {
+                        break;
+                    }
+                }
+            }
+            chosen.push(best);
+            from = best + 1;
+        }
+        Some(chosen)
+    }
+
+    /// How many times over `select` can go along the bank before stacking is quicker
+    const SCAN_LIMIT: usize = 16;
+
+    /// `select`, in one pass however long the bank
+    ///
+    /// Going along the bank, a battery beats any lower one chosen before it, as long as there
+    /// are enough batteries left to drop it and still turn on `k`. The chosen batteries form a
+    /// stack whose top gets popped while that's the case, so each battery is pushed and popped
+    /// at most once.
+    fn select_stacked(&self, k: usize) -> Option<Vec<usize>> {
+        let mut droppable = self.batteries.len().checked_sub(k)?;
+        let mut chosen: Vec<usize> = Vec::with_capacity(self.batteries.len());
+        for (i, battery) in self.batteries.iter().enumerate() {
+            while droppable > 0
+                && let Some(&last) = chosen.last()
+                && self.batteries[last] < *battery
+            {
+                chosen.pop();
+                droppable -= 1;
+            }
+            chosen.push(i);
+        }
+        chosen.truncate(k);
+        Some(chosen)
+    }
+
+    /// The highest joltage we can make by turning on exactly `k` batteries, or None if there
+    /// aren't that many
+    pub fn max_joltage(&self, k: usize) -> Option<Joltage> {
+        let chosen = self.select(k)?;
+        Some(Joltage::from_digits(
+            chosen.into_iter().map(|i| self.batteries[i] as u8),
+        ))
+    }
+
+    /// The latest position each of `k` batteries could be at under `constraints`, indexed by
+    /// how many batteries there are from it to the end, or None if `k` don't fit
+    ///
+    /// Every constraint says how far a battery has to be after an earlier one, so packing the
+    /// batteries against the end of the bank gives each one the latest position it can have in
+    /// any choice. That works back from the end: a battery has to be `gap` before the one after
+    /// it, and `width` before the one `most` after it.
+    fn latest(&self, k: usize, constraints: &Constraints) -> Option<Vec<usize>> {
+        let mut latest = vec![0; k + 1];
+        for r in 1..=k {
+            latest[r] = if r == 1 {
+                self.batteries.len().checked_sub(1)?
+            } else {
+                latest[r - 1].checked_sub(constraints.gap)?
+            };
+            if let Some(window) = constraints.window
+                && r > window.most
+            {
+                latest[r] = latest[r].min(latest[r - window.most].checked_sub(window.width)?);
+            }
+        }
+        Some(latest)
+    }
+
+    /// The positions of the `k` batteries making the highest joltage under `constraints`, in
+    /// order, or None if there's no way to turn on that many
+    ///
+    /// Without constraints this is `select`. Otherwise each battery is chosen in turn as the
+    /// highest, and then earliest, one after the earliest position the batteries already chosen
+    /// allow, and no later than its position in `latest`.
+    ///
+    /// That's as good as trying every choice, which is why this needs no table of best
+    /// joltages. Every choice has the `t`th battery no later than `latest[k - t]`, and a choice
+    /// that does can always be finished: the next battery's earliest position is `gap` after
+    /// this one and `width` after the one `most` before it, and `latest` was built so that both
+    /// are at or before the next battery's own latest position. So the batteries the greedy
+    /// can pick at each step are exactly the ones some choice picks there. Joltages of the same
+    /// length compare digit by digit, so a best choice has the highest battery it can at each
+    /// step. If it takes one later than the greedy's, of the same digit, moving it back to the
+    /// greedy's only puts more room between it and every battery after, so the rest of that
+    /// choice still fits, and the same goes for each step after.
+    fn select_within(&self, k: usize, constraints: &Constraints) -> Option<Vec<usize>> {
+        if constraints.is_none() {
+            return self.select(k);
+        }
+        let latest = self.latest(k, constraints)?;
+        let mut chosen: Vec<usize> = Vec::with_capacity(k);
+        for t in 0..k {
+            let mut from = chosen.last().map_or(0, |last| last + constraints.gap);
+            if let Some(window) = constraints.window
+                && t >= window.most
+            {
+                from = from.max(chosen[t - window.most] + window.width);
+            }
+            let best = (from..=latest[k - t])
+                .rev()
+                .max_by_key(|&i| self.batteries[i])
+                .unwrap();
+            chosen.push(best);
+        }
+        Some(chosen)
+    }
+
+    /// The highest joltage we can make by turning on exactly `k` batteries under
+    /// `constraints`, or None if there's no way to turn on that many
+    pub fn max_joltage_within(&self, k: usize, constraints: &Constraints) -> Option<Joltage> {
+        let chosen = self.select_within(k, constraints)?;
+        Some(Joltage::from_digits(
+            chosen.into_iter().map(|i| self.batteries[i] as u8),
+        ))
+    }
+
+    /// Why `k` batteries can't be turned on in this bank
+    fn error(&self, k: usize, constraints: &Constraints) -> ParseError {
+        let expected = if self.batteries.len() < k {
+            format!("a bank of at least {} batteries", k)
+        } else {
+            format!("a bank with room for {} batteries {}", k, constraints)
+        };
+        ParseError {
+            line: self.line,
+            column: 1,
+            expected,
+            found: self.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Bank {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for battery in self.batteries.iter() {
+            let _ = write!(f, "{}", battery);
+        }
+        Ok(())
+    }
+}
+
+/// How to pick out the chosen batteries when explaining a bank
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Highlight {
+    /// in bold green, for terminals
+    Ansi,
+    /// in square brackets, for everywhere else
+    Brackets,
+}
+
+impl Bank {
+    /// The bank with the `k` batteries making its highest joltage under `constraints` picked
+    /// out
+    pub fn highlight(&self, k: usize, constraints: &Constraints, highlight: Highlight) -> String {
+        let chosen = self.select_within(k, constraints).unwrap_or_default();
+        let mut chosen = chosen.iter().peekable();
+        let mut ret = String::new();
+        for (i, battery) in self.batteries.iter().enumerate() {
+            if chosen.next_if_eq(&&i).is_none() {
+                ret += &battery.to_string();
+                continue;
+            }
+            ret += &match highlight {
+                Highlight::Ansi => format!("\x1b[1;32m{}\x1b[0m", battery),
+                Highlight::Brackets => format!("[{}]", battery),
+            };
+        }
+        ret
+    }
+}
+
+pub struct PowerSupply {
+    banks: Vec<Bank>,
+}
+
+/// Write out every bank with the batteries chosen for it and the joltage they make
+pub fn explain(
+    out: &mut impl Write,
+    supply: &PowerSupply,
+    k: usize,
+    constraints: &Constraints,
+    highlight: Highlight,
+) -> io::Result<()> {
+    for bank in supply.banks.iter() {
+        writeln!(
+            out,
+            "{} {}",
+            bank.highlight(k, constraints, highlight),
+            bank.max_joltage_within(k, constraints).unwrap_or_default()
+        )?;
+    }
+    Ok(())
+}
+
+impl PowerSupply {
+    fn new() -> Self {
+        Self { banks: Vec::new() }
+    }
+    fn add(&mut self, bank: Bank) {
+        self.banks.push(bank);
+    }
+}
+
+impl Day03 {
+    /// The highest joltage from turning on `k` batteries in every bank, or an error pointing at
+    /// a bank without room for them
+    ///
+    /// Each bank's batteries are added straight onto a running total rather than made into a
+    /// joltage of their own, which would be an allocation a bank.
+    fn total(&self, supply: &PowerSupply, k: usize) -> Result<Joltage, ParseError> {
+        supply
+            .banks
+            .par_iter()
+            .try_fold(Joltage::default, |mut total, bank| {
+                let chosen = bank
+                    .select_within(k, &self.constraints)
+                    .ok_or_else(|| bank.error(k, &self.constraints))?;
+                total.add_digits(chosen.iter().rev().map(|&i| bank.batteries[i] as u8));
+                Ok(total)
+            })
+            .try_reduce(Joltage::default, |l, r| Ok(l + r))
+    }
+}
+
+impl Solution for Day03 {
+    type Parsed = PowerSupply;
+    type Part1 = Result<Joltage, ParseError>;
+    type Part2 = Result<Joltage, ParseError>;
+
+    fn parse(&self, input: &str) -> Result<PowerSupply, ParseError> {
+        let mut supply = PowerSupply::new();
+        for line in input::lines(input) {
+            let mut bank = Bank::new(line.number);
+            for (char, at) in line.chars() {
+                let digit = char.to_digit(10).ok_or_else(|| line.error(at, "a digit"))?;
+                bank.add(digit as usize)
+            }
+            supply.add(bank);
+        }
+        Ok(supply)
+    }
+
+    fn part1(&self, supply: &PowerSupply) -> Result<Joltage, ParseError> {
+        self.total(supply, self.part1)
+    }
+
+    fn part2(&self, supply: &PowerSupply) -> Result<Joltage, ParseError> {
+        self.total(supply, self.part2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: [&str; 4] = [
+        "987654321111111",
+        "811111111111119",
+        "234234234234278",
+        "818181911112111",
+    ];
+
+    #[test]
+    fn part1_given() {
+        println!("{}", EXAMPLE.join("\n"));
+        assert_eq!(
+            Day03::default().part1(&Day03::default().parse(&EXAMPLE.join("\n")).unwrap()),
+            Ok(Joltage::from(357))
+        );
+    }
+
+    #[test]
+    fn part2_given() {
+        println!("{}", EXAMPLE.join("\n"));
+        assert_eq!(
+            Day03::default().part2(&Day03::default().parse(&EXAMPLE.join("\n")).unwrap()),
+            Ok(Joltage::from(3121910778619))
+        );
+    }
+
+    /// Trying every way of turning on `k` batteries
+    fn brute_force(bank: &Bank, k: usize, from: usize) -> Option<usize> {
+        if k == 0 {
+            return Some(0);
+        }
+        (from..bank.batteries.len())
+            .filter_map(|i| {
+                let rest = brute_force(bank, k - 1, i + 1)?;
+                Some(bank.batteries[i] * 10usize.pow(k as u32 - 1) + rest)
+            })
+            .max()
+    }
+
+    #[test]
+    fn any_k() {
+        let supply = Day03::default().parse(&EXAMPLE.join("\n")).unwrap();
+        for bank in supply.banks.iter() {
+            for k in 1..=6 {
+                assert_eq!(
+                    bank.max_joltage(k),
+                    brute_force(bank, k, 0).map(|j| Joltage::from(j as u128)),
+                    "{} {}",
+                    bank,
+                    k
+                );
+            }
+            assert_eq!(
+                bank.max_joltage(15).map(|j| j.to_string()),
+                Some(bank.to_string())
+            );
+            assert_eq!(bank.max_joltage(16), None);
+            for k in 0..=16 {
+                assert_eq!(bank.select(k), bank.select_stacked(k), "{} {}", bank, k);
+            }
+        }
+        assert_eq!(supply.banks[0].max_joltage(3), Some(Joltage::from(987)));
+        assert_eq!(supply.banks[2].max_joltage(4), Some(Joltage::from(4478)));
+
+        // the banks are too short for part 2, which leaves part 1 to go ahead with them
+        let day = Day03 {
+            part2: 16,
+            ..Day03::default()
+        };
+        let supply = day.parse(&EXAMPLE.join("\n")).unwrap();
+        assert_eq!(day.part1(&supply), Ok(Joltage::from(357)));
+        let error = day.part2(&supply).unwrap_err();
+        assert_eq!(
+            (error.line, error.expected.as_str()),
+            (1, "a bank of at least 16 batteries")
+        );
+        let error = day.part2(&day.parse("98765432111111111\n12\n").unwrap());
+        assert_eq!(error.unwrap_err().line, 2);
+    }
+
+    #[test]
+    fn big_joltages() {
+        let bank = "9".repeat(150) + &"1".repeat(50);
+        let day = Day03 {
+            part1: 120,
+            part2: 200,
+            ..Day03::default()
+        };
+        let supply = day
+            .parse(&[bank.as_str(), bank.as_str()].join("\n"))
+            .unwrap();
+        assert_eq!(
+            day.part1(&supply).unwrap().to_string(),
+            "1".to_string() + &"9".repeat(119) + "8"
+        );
+        assert_eq!(
+            day.part2(&supply).unwrap().to_string(),
+            "1".to_string() + &"9".repeat(149) + "8" + &"2".repeat(50)
+        );
+
+        let sum: Joltage = [u128::MAX, u128::MAX, 1]
+            .into_iter()
+            .map(Joltage::from)
+            .sum();
+        assert_eq!(sum.to_string(), "680564733841876926926749214863536422911");
+        assert_eq!(
+            Joltage::from_digits([0, 0, 4, 2].into_iter()),
+            Joltage::from(42)
+        );
+        assert_eq!(Joltage::default().to_string(), "0");
+    }
+
+    #[test]
+    fn explained() {
+        let supply = Day03::default().parse(&EXAMPLE.join("\n")).unwrap();
+        let mut out = Vec::new();
+        explain(
+            &mut out,
+            &supply,
+            2,
+            &Constraints::NONE,
+            Highlight::Brackets,
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "[9][8]7654321111111 98\n\
+             [8]1111111111111[9] 89\n\
+             2342342342342[7][8] 78\n\
+             818181[9]1111[2]111 92\n"
+        );
+        assert_eq!(
+            supply.banks[1].highlight(2, &Constraints::NONE, Highlight::Ansi),
+            "\x1b[1;32m8\x1b[0m1111111111111\x1b[1;32m9\x1b[0m"
+        );
+    }
+
+    /// Trying every way of turning on `k` batteries that keeps to `constraints`
+    fn brute_force_within(bank: &Bank, k: usize, constraints: &Constraints) -> Option<Joltage> {
+        let n = bank.batteries.len();
+        let allowed = |chosen: &[usize]| {
+            chosen.windows(2).all(|w| w[1] - w[0] >= constraints.gap)
+                && constraints.window.is_none_or(|window| {
+                    chosen
+                        .windows(window.most + 1)
+                        .all(|w| w[window.most] - w[0] >= window.width)
+                })
+        };
+        (0u32..1 << n)
+            .filter(|mask| mask.count_ones() as usize == k)
+            .map(|mask| {
+                (0..n)
+                    .filter(|i| mask & 1 << i != 0)
+                    .collect::<Vec<usize>>()
+            })
+            .filter(|chosen| allowed(chosen))
+            .map(|chosen| {
+                chosen
+                    .iter()
+                    .fold(0u128, |ret, &i| ret * 10 + bank.batteries[i] as u128)
+            })
+            .max()
+            .map(Joltage::from)
+    }
+
+    #[test]
+    fn constrained() {
+        let supply = Day03::default().parse(&EXAMPLE.join("\n")).unwrap();
+        let mut rng = common::reference::Rng::new(3);
+        for _ in 0..40 {
+            let constraints = Constraints {
+                gap: 1 + rng.below(3) as usize,
+                window: (rng.below(3) > 0).then(|| Window {
+                    most: 1 + rng.below(3) as usize,
+                    width: 1 + rng.below(6) as usize,
+                }),
+            };
+            for bank in supply.banks.iter() {
+                for k in 1..=5 {
+                    assert_eq!(
+                        bank.max_joltage_within(k, &constraints),
+                        brute_force_within(bank, k, &constraints),
+                        "{} {} {}",
+                        bank,
+                        k,
+                        constraints
+                    );
+                }
+            }
+        }
+        assert_eq!(
+            supply.banks[1].max_joltage_within(12, &Constraints::NONE),
+            supply.banks[1].max_joltage(12)
+        );
+
+        let day = Day03 {
+            constraints: Constraints {
+                gap: 2,
+                window: None,
+            },
+            ..Day03::default()
+        };
+        // 12 batteries 2 apart need a bank of 23
+        let supply = day.parse(&EXAMPLE.join("\n")).unwrap();
+        assert_eq!(day.part1(&supply), Ok(Joltage::from(97 + 89 + 48 + 92)));
+        assert_eq!(
+            day.part2(&supply).unwrap_err().expected,
+            "a bank with room for 12 batteries at least 2 apart"
+        );
+
+        assert_eq!("2/5".parse(), Ok(Window { most: 2, width: 5 }));
+        assert!("0/5".parse::<Window>().is_err());
+        assert!("2in5".parse::<Window>().is_err());
+    }
+
+    #[test]
+    fn answers() {
+        common::answers::verify(&Day03::default(), env!("CARGO_MANIFEST_DIR"));
+    }
+}
@@ -1,127 +1,81 @@
-use clap::Parser;
+use clap::{CommandFactory, Parser, error::ErrorKind};
 use clio::Input;
-use std::io::{self, BufReader, prelude::*};
+use common::Part;
+use common::grid::{Neighborhood, Shape};
+use common::output::{Format, report};
+use day04::{Day04, Mode};
+use std::io::{self, prelude::*};
+use std::process::ExitCode;
 
 #[derive(Parser)]
 struct Opt {
     /// Input file, use '-' for stdin
     #[clap(value_parser, default_value = "-")]
     input: Input,
-}
 
-#[derive(Clone, Copy, Debug)]
-struct Paper {
-    x: usize,
-    y: usize,
-}
-impl Paper {
-    fn nearby(&self, grid: &Vec<Vec<Option<Paper>>>) -> usize {
-        let mut ret = 0;
-        let checks: [isize; 3] = [-1, 0, 1];
-        for offset_y in checks.iter() {
-            for offset_x in checks.iter() {
-                if self.offset(*offset_x, *offset_y, grid).is_some() {
-                    ret += 1;
-                }
-            }
-        }
-        ret
-    }
-    fn offset(
-        &self,
-        offset_x: isize,
-        offset_y: isize,
-        grid: &Vec<Vec<Option<Paper>>>,
-    ) -> Option<Paper> {
-        let max_y = grid.len() as isize;
-        let max_x = grid[0].len() as isize;
-        if offset_x < 0 {
-            if self.x == 0 {
-                return None;
-            }
-        }
-        if offset_x + self.x as isize >= max_x {
-            return None;
-        }
-        if offset_y < 0 {
-            if self.y == 0 {
-                return None;
-            }
-        }
-        if offset_y + self.y as isize >= max_y {
-            return None;
-        }
-        if offset_y == 0 && offset_x == 0 {
-            return None;
-        }
-        let x = (self.x as isize + offset_x) as usize;
-        let y = (self.y as isize + offset_y) as usize;
-        grid[y][x]
-    }
-}
-type Grid = Vec<Vec<Option<Paper>>>;
+    /// how to print the answer
+    #[clap(long, value_enum, default_value_t)]
+    format: Format,
 
-fn solve(lines: Vec<String>) -> usize {
-    let mut grid: Grid = Vec::new();
+    /// a roll can be taken when fewer than this many rolls are around it
+    #[clap(long, default_value_t = Day04::default().threshold)]
+    threshold: usize,
 
-    for (y, row) in lines.iter().enumerate() {
-        let mut v_row = Vec::new();
-        for (x, char) in row.chars().enumerate() {
-            if char == '@' {
-                v_row.push(Some(Paper { x, y }));
-            } else {
-                v_row.push(None);
-            }
-        }
-        grid.push(v_row);
-    }
-    grid.iter()
-        .map(|row| {
-            row.iter()
-                .filter(|option| {
-                    if let Some(paper) = option {
-                        paper.nearby(&grid) < 4
-                    } else {
-                        false
-                    }
-                })
-                .count()
-        })
-        .sum()
-}
+    /// which spots count as around a roll
+    #[clap(long, value_enum, default_value_t = Shape::Moore)]
+    neighborhood: Shape,
 
-fn main() -> io::Result<()> {
-    let opt = Opt::parse();
+    /// how many steps away a spot can be and still count as around a roll
+    #[clap(long, default_value_t = 1)]
+    radius: usize,
 
-    let reader = BufReader::new(opt.input);
-    let lines: Vec<String> = reader.lines().flatten().filter(|s| !s.is_empty()).collect();
-    println!("{}", solve(lines));
-    Ok(())
-}
+    /// carry the neighbourhood over the edges of the floor to the other side
+    #[clap(long)]
+    wrap: bool,
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// the character marking a roll of paper
+    #[clap(long, default_value_t = Day04::default().paper)]
+    paper: char,
 
-    fn convert_example(example: &[&str]) -> Vec<String> {
-        example.iter().map(|line| line.to_string()).collect()
-    }
+    /// the character marking an empty spot
+    #[clap(long, default_value_t = Day04::default().empty)]
+    empty: char,
 
-    #[test]
-    fn given() {
-        let example = [
-            "..@@.@@@@.",
-            "@@@.@.@.@@",
-            "@@@@@.@.@@",
-            "@.@@@@..@.",
-            "@@.@@@@.@@",
-            ".@@@@@@@.@",
-            ".@.@.@.@@@",
-            "@.@@@.@@@@",
-            ".@@@@@@@@.",
-            "@.@.@@@.@.",
-        ];
-        println!("{}", example.join("\n"));
-        assert_eq!(solve(convert_example(&example)), 13);
+    /// take rolls away once, or until no more can go
+    #[clap(long, value_enum, default_value_t = Mode::Once)]
+    mode: Mode,
+}
+
+fn main() -> io::Result<ExitCode> {
+    let mut opt = Opt::parse();
+    if opt.paper == opt.empty {
+        Opt::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "rolls and empty spots need different characters",
+            )
+            .exit();
     }
+
+    let mut input = String::new();
+    opt.input.read_to_string(&mut input)?;
+    let day = Day04 {
+        threshold: opt.threshold,
+        neighborhood: Neighborhood {
+            shape: opt.neighborhood,
+            radius: opt.radius,
+            wrap: opt.wrap,
+        },
+        paper: opt.paper,
+        empty: opt.empty,
+        part1: opt.mode,
+        ..Day04::default()
+    };
+    Ok(report(
+        &day,
+        env!("CARGO_PKG_NAME"),
+        Part::One,
+        &input,
+        opt.format,
+    ))
 }
@@ -0,0 +1,246 @@
+use clap::ValueEnum;
+use common::Solution;
+use common::grid::{Grid, Neighborhood};
+use common::parse::ParseError;
+
+/// How many rounds of taking rolls away to go through
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+pub enum Mode {
+    /// just the rolls that can be taken straight away, as part 1 asks
+    Once,
+    /// until no more can be taken, as part 2 asks
+    Fixpoint,
+}
+
+pub struct Day04 {
+    /// a roll can be taken when fewer than this many rolls are around it
+    pub threshold: usize,
+    /// which spots count as around a roll
+    pub neighborhood: Neighborhood,
+    /// the character marking a roll of paper
+    pub paper: char,
+    /// the character marking an empty spot
+    pub empty: char,
+    pub part1: Mode,
+    pub part2: Mode,
+}
+
+impl Default for Day04 {
+    fn default() -> Self {
+        Self {
+            threshold: 4,
+            neighborhood: Neighborhood::MOORE,
+            paper: '@',
+            empty: '.',
+            part1: Mode::Once,
+            part2: Mode::Fixpoint,
+        }
+    }
+}
+
+/// Where the rolls of paper are on the floor
+pub type Floor = Grid<bool>;
+
+impl Day04 {
+    /// How many rolls are around `(x, y)`
+    fn nearby(&self, floor: &Floor, x: usize, y: usize) -> usize {
+        floor
+            .neighbors(x, y, &self.neighborhood)
+            .filter(|&at| floor[at])
+            .count()
+    }
+
+    /// Rolls of paper with too few others around them to stay
+    fn removable(&self, floor: &Floor) -> Vec<(usize, usize)> {
+        floor
+            .iter()
+            .filter(|(_, paper)| **paper)
+            .map(|(at, _)| at)
+            .filter(|&(x, y)| self.nearby(floor, x, y) < self.threshold)
+            .collect()
+    }
+
+    /// Take every roll that can go until none can
+    ///
+    /// Removing a roll only ever leaves its neighbours with fewer rolls around them, so
+    /// every roll that would go in some round goes eventually whatever order they're taken in.
+    /// Each roll keeps a count of the rolls around it, and goes on the worklist when that
+    /// drops below the threshold, which happens at most once.
+    fn fixpoint(&self, floor: &Floor) -> usize {
+        let mut floor = floor.clone();
+        let mut nearby = Grid::new(floor.width(), floor.height(), 0);
+        for ((x, y), &paper) in floor.iter() {
+            if paper {
+                nearby[(x, y)] = self.nearby(&floor, x, y);
+            }
+        }
+        let mut worklist = self.removable(&floor);
+        let mut ret = 0;
+        while let Some((x, y)) = worklist.pop() {
+            floor[(x, y)] = false;
+            ret += 1;
+            for at in floor.neighbors(x, y, &self.neighborhood) {
+                if !floor[at] {
+                    continue;
+                }
+                nearby[at] -= 1;
+                if nearby[at] + 1 == self.threshold {
+                    worklist.push(at);
+                }
+            }
+        }
+        ret
+    }
+
+    /// How many rolls can be taken away
+    pub fn clear(&self, floor: &Floor, mode: Mode) -> usize {
+        match mode {
+            Mode::Once => self.removable(floor).len(),
+            Mode::Fixpoint => self.fixpoint(floor),
+        }
+    }
+}
+
+impl Solution for Day04 {
+    type Parsed = Floor;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Floor, ParseError> {
+        let expected = format!("'{}' or '{}'", self.paper, self.empty);
+        Grid::parse(input, &expected, |c| {
+            if c == self.paper {
+                Some(true)
+            } else if c == self.empty {
+                Some(false)
+            } else {
+                None
+            }
+        })
+    }
+
+    fn part1(&self, floor: &Floor) -> usize {
+        self.clear(floor, self.part1)
+    }
+
+    fn part2(&self, floor: &Floor) -> usize {
+        self.clear(floor, self.part2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::grid::Shape;
+    use common::reference::Rng;
+
+    const EXAMPLE: [&str; 10] = [
+        "..@@.@@@@.",
+        "@@@.@.@.@@",
+        "@@@@@.@.@@",
+        "@.@@@@..@.",
+        "@@.@@@@.@@",
+        ".@@@@@@@.@",
+        ".@.@.@.@@@",
+        "@.@@@.@@@@",
+        ".@@@@@@@@.",
+        "@.@.@@@.@.",
+    ];
+
+    #[test]
+    fn part1_given() {
+        println!("{}", EXAMPLE.join("\n"));
+        assert_eq!(
+            Day04::default().part1(&Day04::default().parse(&EXAMPLE.join("\n")).unwrap()),
+            13
+        );
+    }
+
+    #[test]
+    fn part2_given() {
+        println!("{}", EXAMPLE.join("\n"));
+        assert_eq!(
+            Day04::default().part2(&Day04::default().parse(&EXAMPLE.join("\n")).unwrap()),
+            43
+        );
+    }
+
+    /// The old way of clearing the floor, a round of removals at a time
+    fn rounds(day: &Day04, floor: &Floor) -> usize {
+        let mut floor = floor.clone();
+        let mut ret = 0;
+        loop {
+            let to_remove = day.removable(&floor);
+            if to_remove.is_empty() {
+                return ret;
+            }
+            for at in to_remove.iter() {
+                floor[*at] = false;
+                ret += 1;
+            }
+        }
+    }
+
+    #[test]
+    fn worklist() {
+        let mut rng = Rng::new(4);
+        for _ in 0..20 {
+            let input: Vec<String> = (0..30)
+                .map(|_| {
+                    (0..40)
+                        .map(|_| if rng.below(3) > 0 { '@' } else { '.' })
+                        .collect()
+                })
+                .collect();
+            let day = Day04 {
+                threshold: 1 + rng.below(8) as usize,
+                neighborhood: Neighborhood {
+                    shape: if rng.below(2) == 0 {
+                        Shape::Moore
+                    } else {
+                        Shape::VonNeumann
+                    },
+                    radius: 1 + rng.below(2) as usize,
+                    wrap: rng.below(2) == 0,
+                },
+                ..Day04::default()
+            };
+            let floor = day.parse(&input.join("\n")).unwrap();
+            assert_eq!(
+                day.part2(&floor),
+                rounds(&day, &floor),
+                "{:?}\n{}",
+                day.neighborhood,
+                input.join("\n")
+            );
+        }
+    }
+
+    #[test]
+    fn other_rules() {
+        let input = EXAMPLE.join("\n").replace('@', "#").replace('.', " ");
+        let day = Day04 {
+            paper: '#',
+            empty: ' ',
+            ..Day04::default()
+        };
+        let floor = day.parse(&input).unwrap();
+        assert_eq!((day.part1(&floor), day.part2(&floor)), (13, 43));
+        assert_eq!(day.clear(&floor, Mode::Fixpoint), 43);
+        let error = day.parse(&EXAMPLE.join("\n")).unwrap_err();
+        assert_eq!(error.expected, "'#' or ' '");
+
+        let day = Day04 {
+            neighborhood: Neighborhood::VON_NEUMANN,
+            threshold: 2,
+            ..Day04::default()
+        };
+        let floor = day.parse(&EXAMPLE.join("\n")).unwrap();
+        assert_eq!((day.part1(&floor), day.part2(&floor)), (11, 16));
+    }
+
+    #[test]
+    fn answers() {
+        common::answers::verify(&Day04::default(), env!("CARGO_MANIFEST_DIR"));
+    }
+}
@@ -0,0 +1,165 @@
+use common::Solution;
+use common::input;
+use common::parse::ParseError;
+
+#[derive(Default)]
+pub struct Day05;
+
+pub struct Inventory {
+    fresh: Vec<(usize, usize)>,
+    ids: Vec<usize>,
+}
+
+fn is_in(id: usize, range: &(usize, usize)) -> bool {
+    id >= range.0 && id <= range.1
+}
+
+fn is_fresh(id: usize, fresh: &[(usize, usize)]) -> bool {
+    for range in fresh.iter() {
+        if is_in(id, range) {
+            return true;
+        }
+    }
+    false
+}
+
+fn merge(
+    indexes: Vec<usize>,
+    to_add: (usize, usize),
+    ranges: &mut Vec<(usize, usize)>,
+) -> Vec<(usize, usize)> {
+    //eprintln!(
+    //    "Adding {:?} to {:?}, merging with indexes {:?}",
+    //    to_add, ranges, indexes
+    //);
+    let mut new: (usize, usize) = to_add;
+    let mut updated = Vec::new();
+    for i in indexes.iter().rev() {
+        let old = ranges.remove(*i);
+        if is_in(new.0, &old) {
+            //eprintln!("Merging {:?} with {:?} on the right", new, old);
+            new = (old.0, new.1);
+            //eprintln!("Got: {:?}", new);
+        }
+        if is_in(new.1, &old) {
+            //eprintln!("Merging {:?} with {:?} on the left", new, old);
+            new = (new.0, old.1);
+            //eprintln!("Got: {:?}", new);
+        }
+    }
+    //eprintln!("Pushing: {:?}", new);
+    updated.push(new);
+    for range in ranges.iter_mut() {
+        updated.push(*range);
+    }
+    //eprintln!("Got: {:?}", updated);
+    updated
+}
+
+impl Solution for Day05 {
+    type Parsed = Inventory;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Inventory, ParseError> {
+        let mut sections = input::sections(input).into_iter();
+        let ranges = sections.next().unwrap_or_default();
+        let ids = sections.next().unwrap_or_default();
+        if let Some(extra) = sections.next() {
+            return Err(extra[0].error(extra[0].text, "the end of the input"));
+        }
+        let fresh = ranges
+            .into_iter()
+            .map(|line| {
+                let (left, right) = line
+                    .text
+                    .split_once('-')
+                    .ok_or_else(|| line.error(line.text, "a range like '3-5'"))?;
+                Ok((line.number(left)?, line.number(right)?))
+            })
+            .collect::<Result<_, _>>()?;
+        let ids = input::typed(ids).collect::<Result<_, _>>()?;
+        Ok(Inventory { fresh, ids })
+    }
+
+    fn part1(&self, inventory: &Inventory) -> usize {
+        inventory
+            .ids
+            .iter()
+            .map(|id| is_fresh(*id, &inventory.fresh))
+            .filter(|is_fresh| *is_fresh)
+            .count()
+    }
+
+    fn part2(&self, inventory: &Inventory) -> usize {
+        let mut fresh: Vec<(usize, usize)> = Vec::new();
+        for &(l, r) in inventory.fresh.iter() {
+            let new = (l, r);
+            //eprintln!("Processing pair: {:?}", new);
+            let mut to_merge: Vec<usize> = Vec::new();
+            for (i, range) in fresh.iter().enumerate() {
+                //eprintln!("Checking if {:?} is inside {:?}", new, range);
+                if is_in(r, range) {
+                    to_merge.push(i);
+                }
+                if is_in(l, range) {
+                    to_merge.push(i);
+                }
+                //eprintln!("Checking if {:?} is inside {:?}", range, new);
+                if is_in(range.1, &new) {
+                    to_merge.push(i);
+                }
+                if is_in(range.0, &new) {
+                    to_merge.push(i);
+                }
+            }
+            if !to_merge.is_empty() {
+                to_merge.dedup();
+                //eprintln!("Found indexes needing merge: {:?}", to_merge);
+                fresh = merge(to_merge, new, &mut fresh);
+                continue;
+            }
+            fresh.push(new);
+        }
+        //eprintln!("Final: {:?}", fresh);
+        fresh.iter().map(|(l, r)| (*l..=*r).count()).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: [&str; 11] = [
+        "3-5", "10-14", "16-20", "12-18", "", "1", "5", "8", "11", "17", "32",
+    ];
+
+    #[test]
+    fn part1_given() {
+        println!("{}", EXAMPLE.join("\n"));
+        assert_eq!(Day05.part1(&Day05.parse(&EXAMPLE.join("\n")).unwrap()), 3);
+    }
+
+    #[test]
+    fn part2_given() {
+        println!("{}", EXAMPLE.join("\n"));
+        assert_eq!(Day05.part2(&Day05.parse(&EXAMPLE.join("\n")).unwrap()), 14);
+    }
+    #[test]
+    fn part2_problem_children() {
+        let example = [
+            "54611041342437-57224634168756",
+            "51945266971870-59698236253713",
+        ];
+        println!("{}", example.join("\n"));
+        assert_eq!(
+            Day05.part2(&Day05.parse(&example.join("\n")).unwrap()),
+            7752969281844
+        );
+    }
+
+    #[test]
+    fn answers() {
+        common::answers::verify(&Day05, env!("CARGO_MANIFEST_DIR"));
+    }
+}
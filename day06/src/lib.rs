@@ -0,0 +1,255 @@
+use common::Solution;
+use common::input;
+use common::parse::{Line, ParseError};
+use std::ops::Range;
+
+#[derive(Default)]
+pub struct Day06;
+
+/// What to do to a problem's numbers
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Operation {
+    Add,
+    Multiply,
+}
+
+impl Operation {
+    fn apply(&self, l: usize, r: usize) -> usize {
+        match self {
+            Operation::Add => l + r,
+            Operation::Multiply => l * r,
+        }
+    }
+
+    /// Combine every number of a problem, of which there's always at least one
+    fn fold(&self, numbers: &[usize]) -> usize {
+        numbers[1..]
+            .iter()
+            .fold(numbers[0], |ret, &n| self.apply(ret, n))
+    }
+}
+
+/// One problem on the worksheet, with its numbers read both ways
+struct Problem {
+    operation: Operation,
+    /// a number from each row, top to bottom, then a number from each column, read down it,
+    /// left to right
+    numbers: Vec<usize>,
+    /// how many of `numbers` come from rows
+    rows: usize,
+}
+
+impl Problem {
+    fn rows(&self) -> &[usize] {
+        &self.numbers[..self.rows]
+    }
+
+    fn columns(&self) -> &[usize] {
+        &self.numbers[self.rows..]
+    }
+}
+
+pub struct Worksheet {
+    problems: Vec<Problem>,
+}
+
+/// The runs of columns with a digit in them, one for each problem, as `start..end`
+fn problem_columns(lines: &[Line]) -> Vec<Range<usize>> {
+    let width = lines.iter().map(|line| line.text.len()).max().unwrap_or(0);
+    let blank = |x: usize| {
+        lines
+            .iter()
+            .all(|line| line.text.as_bytes().get(x).is_none_or(|c| *c == b' '))
+    };
+    let mut ret: Vec<Range<usize>> = Vec::new();
+    for x in (0..width).filter(|&x| !blank(x)) {
+        match ret.last_mut() {
+            Some(columns) if columns.end == x => columns.end = x + 1,
+            _ => ret.push(x..x + 1),
+        }
+    }
+    ret
+}
+
+/// The character at column `x` of `line`, or the end of the line if it's not that long
+fn cell<'a>(line: &Line<'a>, x: usize) -> &'a str {
+    line.text.get(x..x + 1).unwrap_or(line.end())
+}
+
+/// The number written down column `x`, from its first digit to its last
+fn column_number(lines: &[Line], x: usize) -> Result<usize, ParseError> {
+    // past the end of a line counts as a space
+    let digit = |line: &Line| line.text.as_bytes().get(x).copied().filter(|c| *c != b' ');
+    let top = lines.iter().position(|line| digit(line).is_some()).unwrap();
+    let bottom = lines
+        .iter()
+        .rposition(|line| digit(line).is_some())
+        .unwrap();
+    let mut number: usize = 0;
+    for line in lines[top..=bottom].iter() {
+        let Some(c) = digit(line) else {
+            let expected = "a digit, as the column's number carries on below";
+            return Err(line.error(cell(line, x), expected));
+        };
+        number = number
+            .checked_mul(10)
+            .and_then(|number| number.checked_add((c - b'0') as usize))
+            .ok_or_else(|| {
+                let line = &lines[top];
+                line.error(cell(line, x), "a column of digits that fits in a number")
+            })?;
+    }
+    Ok(number)
+}
+
+impl Solution for Day06 {
+    type Parsed = Worksheet;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Worksheet, ParseError> {
+        let mut lines: Vec<_> = input::lines(input).collect();
+        let Some(last_line) = lines.pop() else {
+            return Err(ParseError::end(input, "a row of '+' and '*'"));
+        };
+        for line in lines.iter() {
+            if line.text.bytes().any(|c| !c.is_ascii_digit() && c != b' ')
+                && let Some((_, at)) = line.chars().find(|(c, _)| !c.is_ascii_digit() && *c != ' ')
+            {
+                return Err(line.error(at, "a digit"));
+            }
+        }
+        let operations: Vec<Operation> = last_line
+            .text
+            .split_ascii_whitespace()
+            .map(|s| match s {
+                "+" => Ok(Operation::Add),
+                "*" => Ok(Operation::Multiply),
+                _ => Err(last_line.error(s, "'+' or '*'")),
+            })
+            .collect::<Result<_, _>>()?;
+        if lines.is_empty() {
+            return Err(last_line.error(last_line.text, "rows of numbers above the symbols"));
+        }
+        for line in lines.iter() {
+            let numbers = line.text.split_ascii_whitespace().count();
+            if numbers != operations.len() {
+                let expected = format!("{} numbers, one for each symbol", operations.len());
+                return Err(line.error(line.text, expected));
+            }
+        }
+        let columns = problem_columns(&lines);
+        if columns.len() != operations.len() {
+            let expected = format!(
+                "a symbol for each of the {} columns of numbers",
+                columns.len()
+            );
+            return Err(last_line.error(last_line.text, expected));
+        }
+
+        let mut problems = Vec::with_capacity(columns.len());
+        for (operation, columns) in operations.into_iter().zip(columns) {
+            let mut numbers = Vec::with_capacity(lines.len() + columns.len());
+            for line in lines.iter() {
+                let len = line.text.len();
+                let field = &line.text[columns.start.min(len)..columns.end.min(len)];
+                numbers.push(line.number(field.trim_ascii())?);
+            }
+            for x in columns {
+                numbers.push(column_number(&lines, x)?);
+            }
+            problems.push(Problem {
+                operation,
+                numbers,
+                rows: lines.len(),
+            });
+        }
+        Ok(Worksheet { problems })
+    }
+
+    fn part1(&self, worksheet: &Worksheet) -> usize {
+        worksheet
+            .problems
+            .iter()
+            .map(|problem| problem.operation.fold(problem.rows()))
+            .sum()
+    }
+
+    fn part2(&self, worksheet: &Worksheet) -> usize {
+        worksheet
+            .problems
+            .iter()
+            .map(|problem| problem.operation.fold(problem.columns()))
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: [&str; 4] = [
+        "123 328  51 64",
+        " 45 64  387 23",
+        "  6 98  215 314",
+        "*   +   *   +",
+    ];
+
+    #[test]
+    fn part1_given() {
+        println!("{}", EXAMPLE.join("\n"));
+        assert_eq!(
+            Day06.part1(&Day06.parse(&EXAMPLE.join("\n")).unwrap()),
+            4277556
+        );
+    }
+
+    #[test]
+    fn part2_given() {
+        println!("{}", EXAMPLE.join("\n"));
+        assert_eq!(
+            Day06.part2(&Day06.parse(&EXAMPLE.join("\n")).unwrap()),
+            3263827
+        );
+    }
+
+    #[test]
+    fn mismatched() {
+        let error = Day06.parse("1 2\n3\n+ +").err().unwrap();
+        assert_eq!(
+            (error.line, error.expected.as_str()),
+            (2, "2 numbers, one for each symbol")
+        );
+        let error = Day06.parse("+").err().unwrap();
+        assert_eq!(error.expected, "rows of numbers above the symbols");
+        let error = Day06.parse("12 3\n4 56\n+ *").err().unwrap();
+        assert_eq!(
+            (error.line, error.expected.as_str()),
+            (3, "a symbol for each of the 1 columns of numbers")
+        );
+    }
+
+    #[test]
+    fn layout() {
+        // any number of blank columns can sit between problems
+        let worksheet = Day06.parse("12    3\n 4   56\n*     +").unwrap();
+        assert_eq!(Day06.part1(&worksheet), 12 * 4 + 3 + 56);
+        assert_eq!(Day06.part2(&worksheet), 24 + 5 + 36);
+
+        // 20 digits don't fit
+        let error = Day06
+            .parse(&format!("{} 1\n1 1\n+ +", "9".repeat(20)))
+            .err()
+            .unwrap();
+        assert_eq!((error.line, error.column), (1, 1));
+        assert_eq!(error.expected, "a number");
+        // down a column, a number can't have a gap in it
+        let error = Day06.parse("12 3\n 4 5\n1  6\n+ +").err().unwrap();
+        assert_eq!((error.line, error.column), (2, 1));
+    }
+
+    #[test]
+    fn answers() {
+        common::answers::verify(&Day06, env!("CARGO_MANIFEST_DIR"));
+    }
+}
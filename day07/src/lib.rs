@@ -0,0 +1,207 @@
+use common::Solution;
+use common::grid::Grid;
+use common::input;
+use common::parse::ParseError;
+use moka::sync::Cache;
+
+#[derive(Default)]
+pub struct Day07;
+
+pub struct Manifold {
+    grid: Grid<char>,
+    start: (usize, usize),
+}
+
+/// Count every splitter the beam hits, only counting each splitter once
+fn splits(
+    grid: &Grid<char>,
+    beam: (usize, usize),
+    cache: &mut Cache<(usize, usize), usize>,
+) -> usize {
+    if beam.1 == grid.height() - 1 {
+        return 0;
+    }
+    if cache.contains_key(&beam) {
+        return 0;
+    }
+    let ret = match grid[beam] {
+        '.' => splits(grid, (beam.0, beam.1 + 1), cache),
+        '^' => {
+            1 + splits(grid, (beam.0 - 1, beam.1 + 1), cache)
+                + splits(grid, (beam.0 + 1, beam.1 + 1), cache)
+        }
+        _ => panic!(
+            "Somehow hit position '{:?}' at ({}, {})",
+            grid[beam], beam.0, beam.1
+        ),
+    };
+    cache.insert((beam.0, beam.1), ret);
+    ret
+}
+
+/// Count every distinct path the beam could take to the bottom of the manifold
+fn timelines(
+    grid: &Grid<char>,
+    beam: (usize, usize),
+    cache: &mut Cache<(usize, usize), usize>,
+) -> usize {
+    if beam.1 == grid.height() - 1 {
+        return 1;
+    }
+    if let Some(cached) = cache.get(&beam) {
+        return cached;
+    }
+    let ret = match grid[beam] {
+        '.' => timelines(grid, (beam.0, beam.1 + 1), cache),
+        '^' => {
+            timelines(grid, (beam.0 - 1, beam.1 + 1), cache)
+                + timelines(grid, (beam.0 + 1, beam.1 + 1), cache)
+        }
+        _ => panic!(
+            "Somehow hit position '{:?}' at ({}, {})",
+            grid[beam], beam.0, beam.1
+        ),
+    };
+    cache.insert((beam.0, beam.1), ret);
+    ret
+}
+
+impl Solution for Day07 {
+    type Parsed = Manifold;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Manifold, ParseError> {
+        let grid = Grid::parse(input, "'.', '^' or 'S'", |c| {
+            matches!(c, '.' | '^' | 'S').then_some(c)
+        })?;
+        // the grid's rows are the input's non-blank lines, so errors can point back at them
+        let error = |(x, y): (usize, usize), expected: &str| {
+            let line = input::lines(input).nth(y).unwrap();
+            line.error(&line.text[x..x + 1], expected)
+        };
+        let Some((start, _)) = grid.iter().find(|(_, c)| **c == 'S') else {
+            return Err(ParseError::end(input, "a start 'S'"));
+        };
+        if start.1 + 1 == grid.height() {
+            return Err(error(start, "a start 'S' above the bottom row"));
+        }
+        let edge = grid
+            .iter()
+            .find(|((x, _), c)| **c == '^' && (*x == 0 || *x + 1 == grid.width()));
+        if let Some((at, _)) = edge {
+            return Err(error(at, "a splitter '^' away from the sides"));
+        }
+        Ok(Manifold {
+            grid,
+            start: (start.0, start.1 + 1),
+        })
+    }
+
+    fn part1(&self, manifold: &Manifold) -> usize {
+        let cache = Cache::new(10_000);
+        splits(&manifold.grid, manifold.start, &mut cache.clone())
+    }
+
+    fn part2(&self, manifold: &Manifold) -> usize {
+        let cache = Cache::new(10_000);
+        timelines(&manifold.grid, manifold.start, &mut cache.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Part;
+    use common::reference::{self, Rng};
+
+    const EXAMPLE: [&str; 16] = [
+        ".......S.......",
+        "...............",
+        ".......^.......",
+        "...............",
+        "......^.^......",
+        "...............",
+        ".....^.^.^.....",
+        "...............",
+        "....^.^...^....",
+        "...............",
+        "...^.^...^.^...",
+        "...............",
+        "..^...^.....^..",
+        "...............",
+        ".^.^.^.^.^...^.",
+        "...............",
+    ];
+
+    #[test]
+    fn part1_given() {
+        println!("{}", EXAMPLE.join("\n"));
+        assert_eq!(Day07.part1(&Day07.parse(&EXAMPLE.join("\n")).unwrap()), 21);
+    }
+
+    #[test]
+    fn part2_given() {
+        println!("{}", EXAMPLE.join("\n"));
+        assert_eq!(Day07.part2(&Day07.parse(&EXAMPLE.join("\n")).unwrap()), 40);
+    }
+
+    #[test]
+    fn degenerate() {
+        let error = Day07.parse("...\n.S.\n").err().unwrap();
+        assert_eq!((error.line, error.column), (2, 2));
+        let error = Day07.parse(".S.\n...\n^..\n").err().unwrap();
+        assert_eq!(
+            (error.line, error.expected.as_str()),
+            (3, "a splitter '^' away from the sides")
+        );
+    }
+
+    #[test]
+    fn answers() {
+        common::answers::verify(&Day07, env!("CARGO_MANIFEST_DIR"));
+    }
+
+    /// A manifold like the puzzle's, with splitters kept off the edges so beams never leave the
+    /// grid, and never side by side, which the puzzle's inputs avoid too
+    fn generate(seed: u64) -> String {
+        let mut rng = Rng::new(seed);
+        let width = 31;
+        let mut rows = vec![format!("{:.^31}", "S"), ".".repeat(width)];
+        for _ in 0..15 {
+            let mut row = vec!['.'; width];
+            for x in 1..(width - 1) {
+                if row[x - 1] == '.' && rng.below(3) == 0 {
+                    row[x] = '^';
+                }
+            }
+            rows.push(row.into_iter().collect());
+            rows.push(".".repeat(width));
+        }
+        rows.join("\n")
+    }
+
+    #[test]
+    fn reference_part1() {
+        let generated: Vec<String> = (0..5).map(generate).collect();
+        reference::check(
+            &Day07,
+            Part::One,
+            env!("CARGO_MANIFEST_DIR"),
+            "part1.py",
+            &generated,
+        );
+    }
+
+    #[test]
+    fn reference_part2() {
+        let generated: Vec<String> = (0..5).map(generate).collect();
+        reference::check(
+            &Day07,
+            Part::Two,
+            env!("CARGO_MANIFEST_DIR"),
+            "part2.py",
+            &generated,
+        );
+    }
+}
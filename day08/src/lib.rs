@@ -0,0 +1,238 @@
+use common::Solution;
+use common::input;
+use common::parse::ParseError;
+use itertools::Itertools;
+use petgraph::algo::connected_components;
+use petgraph::graph::{NodeIndex, UnGraph};
+use rayon::prelude::*;
+use std::cmp::Reverse;
+use std::collections::HashSet;
+
+pub struct Day08 {
+    /// how many of the closest pairs to connect in part 1
+    pub connections: usize,
+}
+
+impl Default for Day08 {
+    fn default() -> Self {
+        Self { connections: 1000 }
+    }
+}
+
+type JunctionBox = (usize, usize, usize);
+type Pair = (JunctionBox, JunctionBox);
+pub type Playground = Vec<JunctionBox>;
+type Circuit = HashSet<JunctionBox>;
+
+fn euclidean_distance(a: &JunctionBox, b: &JunctionBox) -> usize {
+    let dx = a.0.abs_diff(b.0);
+    let dy = a.1.abs_diff(b.1);
+    let dz = a.2.abs_diff(b.2);
+    (dx.pow(2) + dy.pow(2) + dz.pow(2)).isqrt()
+}
+
+fn pair_in_circuit(pair: Pair, circuit: &Circuit) -> bool {
+    let (l, r) = pair;
+    if circuit.get(&l).is_some() {
+        return true;
+    }
+    if circuit.get(&r).is_some() {
+        return true;
+    }
+    false
+}
+
+impl Solution for Day08 {
+    type Parsed = Playground;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Playground, ParseError> {
+        let playground: Playground = input::lines(input)
+            .map(|line| {
+                let coords: Vec<usize> = line
+                    .text
+                    .splitn(3, ",")
+                    .map(|s| line.number(s))
+                    .collect::<Result<_, _>>()?;
+                coords
+                    .into_iter()
+                    .collect_tuple()
+                    .ok_or_else(|| line.error(line.end(), "three coordinates like '1,2,3'"))
+            })
+            .collect::<Result<_, _>>()?;
+        // both parts connect pairs of boxes
+        if playground.len() < 2 {
+            return Err(ParseError::end(input, "at least two junction boxes"));
+        }
+        Ok(playground)
+    }
+
+    fn part1(&self, playground: &Playground) -> usize {
+        // record the distance between all pairs, store them
+        let mut distances: Vec<(Pair, usize)> = playground
+            .iter()
+            .copied()
+            .tuple_combinations()
+            .par_bridge()
+            .map(|pair: Pair| {
+                let distance = euclidean_distance(&pair.0, &pair.1);
+                (pair, distance)
+            })
+            .collect();
+        // sort with the closest at the end
+        distances.sort_by_key(|d| Reverse(d.1));
+
+        // map $count worth of close pairs from the whole playground
+        let mut circuits: Vec<Circuit> = Vec::new();
+        for _ in 0..self.connections {
+            // this is the closest pair, if any are left to connect
+            let Some((pair, _)) = distances.pop() else {
+                break;
+            };
+
+            // check if we already have a circuit with one of these junction boxes in it
+            let mut to_add: Option<usize> = None;
+            for (i, circuit) in circuits.iter().enumerate() {
+                if pair_in_circuit(pair, circuit) {
+                    to_add = Some(i);
+                    break;
+                }
+            }
+            // if we do, add this pair to the circuit, otherwise create a new circuit with the pair
+            if let Some(i) = to_add {
+                circuits[i].insert(pair.0);
+                circuits[i].insert(pair.1);
+            } else {
+                circuits.push(HashSet::from([pair.0, pair.1]));
+            }
+        }
+
+        // merge all connected circuits
+        loop {
+            let mut mergable: Option<(usize, usize)> = None;
+            'outer: for i in 0..circuits.len() {
+                for j in 0..circuits.len() {
+                    if i == j {
+                        continue;
+                    }
+                    let intersection: Vec<&JunctionBox> =
+                        circuits[i].intersection(&circuits[j]).collect();
+                    if !intersection.is_empty() {
+                        mergable = Some((i, j));
+                        break 'outer;
+                    }
+                }
+            }
+            if mergable.is_none() {
+                break;
+            }
+            let (l, r) = mergable.unwrap();
+            let mut left: Circuit;
+            let right: Circuit;
+            if l < r {
+                right = circuits.remove(r);
+                left = circuits.remove(l);
+            } else {
+                left = circuits.remove(l);
+                right = circuits.remove(r);
+            }
+            left.extend(right);
+            circuits.push(left);
+        }
+
+        // sort the circuits with longest first
+        circuits.sort_by_key(|c| Reverse(c.len()));
+
+        // multiply the length of the three longest circuits
+        let mut ret = 1;
+        for circuit in circuits.iter().take(3) {
+            ret *= circuit.len();
+        }
+        ret
+    }
+
+    fn part2(&self, playground: &Playground) -> usize {
+        // prepare the graph of indexes
+        let mut circuit = UnGraph::<usize, ()>::new_undirected();
+        let nodes: Vec<NodeIndex> = (0..playground.len()).map(|i| circuit.add_node(i)).collect();
+
+        // record the distance between all pairs, store the indexes in the playground
+        let mut sorted_distances: Vec<((usize, usize), usize)> = playground
+            .iter()
+            .enumerate()
+            .tuple_combinations()
+            .par_bridge()
+            .map(|((i, a), (j, b))| {
+                let distance = euclidean_distance(a, b);
+                ((i, j), distance)
+            })
+            .collect();
+        sorted_distances.par_sort_by(|a, b| b.1.cmp(&a.1));
+
+        // graph all the nodes in the playground
+        loop {
+            let ((l, r), _) = sorted_distances.pop().unwrap();
+            circuit.update_edge(nodes[l], nodes[r], ());
+            if connected_components(&circuit) == 1 {
+                return playground[l].0 * playground[r].0;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: [&str; 20] = [
+        "162,817,812",
+        "57,618,57",
+        "906,360,560",
+        "592,479,940",
+        "352,342,300",
+        "466,668,158",
+        "542,29,236",
+        "431,825,988",
+        "739,650,466",
+        "52,470,668",
+        "216,146,977",
+        "819,987,18",
+        "117,168,530",
+        "805,96,715",
+        "346,949,466",
+        "970,615,88",
+        "941,993,340",
+        "862,61,35",
+        "984,92,344",
+        "425,690,689",
+    ];
+
+    #[test]
+    fn part1_given() {
+        println!("{}", EXAMPLE.join("\n"));
+        let day = Day08 { connections: 10 };
+        assert_eq!(day.part1(&day.parse(&EXAMPLE.join("\n")).unwrap()), 40);
+    }
+
+    #[test]
+    fn part2_given() {
+        println!("{}", EXAMPLE.join("\n"));
+        let day = Day08::default();
+        assert_eq!(day.part2(&day.parse(&EXAMPLE.join("\n")).unwrap()), 25272);
+    }
+
+    #[test]
+    fn degenerate() {
+        let day = Day08::default();
+        let error = day.parse("1,2,3\n").err().unwrap();
+        assert_eq!(error.expected, "at least two junction boxes");
+        // fewer pairs than connections just connects all of them
+        assert_eq!(day.part1(&day.parse("1,2,3\n4,5,6\n").unwrap()), 2);
+    }
+
+    #[test]
+    fn answers() {
+        common::answers::verify(&Day08::default(), env!("CARGO_MANIFEST_DIR"));
+    }
+}
@@ -0,0 +1,238 @@
+use common::Solution;
+use common::grid::Grid;
+use common::input;
+use common::parse::ParseError;
+use itertools::Itertools;
+use rayon::prelude::*;
+use std::cmp::Ordering;
+use std::ops::{Range, RangeInclusive};
+
+#[derive(Default)]
+pub struct Day09;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+enum Tile {
+    Red,
+    Green,
+}
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Coord {
+    x: usize,
+    y: usize,
+}
+type Floor = Grid<Option<Tile>>;
+type Pair<'a> = (&'a Coord, &'a Coord);
+pub type Coords = Vec<Coord>;
+
+fn area(pair: Pair) -> usize {
+    let dx = pair.0.x.abs_diff(pair.1.x) + 1;
+    let dy = pair.0.y.abs_diff(pair.1.y) + 1;
+    dx * dy
+}
+
+fn filled_area(pair: Pair, floor: &Floor) -> usize {
+    let left: &Coord;
+    let right: &Coord;
+    // order the coords left to right
+    if pair.0.x < pair.1.x {
+        left = pair.0;
+        right = pair.1;
+    } else {
+        left = pair.1;
+        right = pair.0;
+    }
+    // determine which vertical direction we should range
+    let vertical: RangeInclusive<usize> = if left.y < right.y {
+        left.y..=right.y
+    } else {
+        right.y..=left.y
+    };
+    // check every coord in the two rays forming the sides of the rectangle beween these two
+    for y in vertical {
+        if floor[(left.x, y)].is_none() {
+            return 0;
+        }
+    }
+    if floor.row(left.y)[left.x..=right.x]
+        .iter()
+        .any(|t| t.is_none())
+    {
+        return 0;
+    }
+
+    // take the area
+    area(pair)
+}
+
+fn lay_green(a: Coord, b: Coord, floor: &mut Floor) {
+    if a.x == b.x {
+        let ty_range: Range<usize> = if a.y < b.y {
+            (a.y + 1)..b.y
+        } else {
+            (b.y + 1)..a.y
+        };
+        for ty in ty_range {
+            floor[(b.x, ty)] = Some(Tile::Green);
+        }
+    } else {
+        let tx_range: Range<usize> = if a.x < b.x {
+            (a.x + 1)..b.x
+        } else {
+            (b.x + 1)..a.x
+        };
+        for tx in tx_range {
+            floor[(tx, b.y)] = Some(Tile::Green);
+        }
+    }
+}
+
+impl Solution for Day09 {
+    type Parsed = Coords;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Coords, ParseError> {
+        let coords: Coords = input::lines(input)
+            .map(|line| {
+                let (x, y) = line
+                    .text
+                    .split_once(',')
+                    .ok_or_else(|| line.error(line.text, "a coordinate like '7,1'"))?;
+                Ok(Coord {
+                    x: line.number(x)?,
+                    y: line.number(y)?,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        // both parts take rectangles between pairs of red tiles
+        if coords.len() < 2 {
+            return Err(ParseError::end(input, "at least two red tiles"));
+        }
+        Ok(coords)
+    }
+
+    fn part1(&self, coords: &Coords) -> usize {
+        // Map the distances between the points
+        let mut distances: Vec<usize> = coords
+            .iter()
+            .tuple_combinations()
+            .par_bridge()
+            .map(area)
+            .collect();
+
+        // sort with the furthest at the end
+        distances.sort();
+        distances.pop().unwrap()
+    }
+
+    fn part2(&self, coords: &Coords) -> usize {
+        let max_x = coords.iter().map(|c| c.x).max().unwrap();
+        let max_y = coords.iter().map(|c| c.y).max().unwrap();
+
+        let mut floor = Floor::new(max_x + 1, max_y + 1, None);
+
+        let mut first: Option<Coord> = None;
+        let mut last: Option<Coord> = None;
+        let mut prev: Option<Coord> = None;
+
+        //eprintln!("Laying tile borders");
+
+        for coord in coords.iter() {
+            if first.is_none() {
+                first = Some(*coord);
+            }
+            floor[(coord.x, coord.y)] = Some(Tile::Red);
+            if let Some(prev_c) = prev {
+                lay_green(*coord, prev_c, &mut floor);
+            }
+            last = Some(*coord);
+            prev = Some(*coord);
+        }
+
+        let last = last.unwrap();
+        let first = first.unwrap();
+        lay_green(last, first, &mut floor);
+
+        //eprintln!("Finding all tiles");
+        let mut filled_tiles: Vec<Coord> = (0..floor.height())
+            .into_par_iter()
+            .map(|y| {
+                floor
+                    .row(y)
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, t)| t.is_some())
+                    .map(move |(x, _)| Coord { x, y })
+                    .collect()
+            })
+            .reduce(Vec::new, |mut a: Vec<Coord>, mut b: Vec<Coord>| {
+                a.append(&mut b);
+                a
+            });
+        filled_tiles.par_sort_by(|a, b| {
+            if a.y > b.y {
+                Ordering::Greater
+            } else if b.y > a.y {
+                Ordering::Less
+            } else if a.x > b.x {
+                Ordering::Greater
+            } else if b.x > a.x {
+                Ordering::Less
+            } else {
+                Ordering::Equal
+            }
+        });
+
+        //eprintln!("Laying tile fill");
+        let mut last: Option<Coord> = None;
+        for tile in filled_tiles.iter() {
+            if let Some(l) = last
+                && tile.y == l.y
+                && tile.x - l.x > 1
+            {
+                lay_green(l, *tile, &mut floor);
+            }
+            last = Some(*tile);
+        }
+
+        //eprintln!("Running area calculations");
+        coords
+            .iter()
+            .tuple_combinations()
+            .par_bridge()
+            .map(|pair| filled_area(pair, &floor))
+            .max()
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: [&str; 8] = ["7,1", "11,1", "11,7", "9,7", "9,5", "2,5", "2,3", "7,3"];
+
+    #[test]
+    fn part1_given() {
+        println!("{}", EXAMPLE.join("\n"));
+        assert_eq!(Day09.part1(&Day09.parse(&EXAMPLE.join("\n")).unwrap()), 50);
+    }
+
+    #[test]
+    fn part2_given() {
+        println!("{}", EXAMPLE.join("\n"));
+        assert_eq!(Day09.part2(&Day09.parse(&EXAMPLE.join("\n")).unwrap()), 24);
+    }
+
+    #[test]
+    fn degenerate() {
+        let error = Day09.parse("7,1\n").err().unwrap();
+        assert_eq!(error.expected, "at least two red tiles");
+    }
+
+    #[test]
+    #[ignore = "part 2 builds a ~10GB floor on the real input"]
+    fn answers() {
+        common::answers::verify(&Day09, env!("CARGO_MANIFEST_DIR"));
+    }
+}
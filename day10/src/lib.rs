@@ -0,0 +1,221 @@
+use common::Solution;
+use common::input;
+use common::parse::ParseError;
+use itertools::Itertools;
+use microlp::{ComparisonOp, LinearExpr, OptimizationDirection, Problem, Variable};
+use rayon::prelude::*;
+use regex::Regex;
+
+#[derive(Default)]
+pub struct Day10;
+
+#[derive(Debug)]
+struct Button {
+    toggles: Vec<usize>,
+}
+#[derive(Debug)]
+pub struct Machine {
+    indicators: Vec<bool>,
+    buttons: Vec<Button>,
+    joltage: Vec<usize>,
+    /// where the machine was in the input, for pointing at one that can't be solved
+    line: usize,
+    text: String,
+}
+pub type Machines = Vec<Machine>;
+
+impl Machine {
+    fn error(&self, expected: &str) -> ParseError {
+        ParseError {
+            line: self.line,
+            column: 1,
+            expected: expected.to_string(),
+            found: self.text.clone(),
+        }
+    }
+
+    fn fewest(&self) -> Option<usize> {
+        //eprintln!("Solving: {:?}", self);
+        for button_combo in self.buttons.iter().powerset() {
+            let mut test = self.indicators.clone();
+            for button in button_combo.iter() {
+                for i in button.toggles.iter() {
+                    test[*i] = !test[*i];
+                }
+            }
+            //eprintln!("Combo: {:?}", button_combo);
+            //eprintln!("Test result: {:?}", test);
+            if !test.into_iter().any(|b| b) {
+                //eprintln!("Solved with {} buttons!", button_combo.len());
+                return Some(button_combo.len());
+            }
+        }
+        None
+    }
+
+    fn fewest_for_joltage(&self) -> Option<usize> {
+        let max = self.joltage.iter().copied().max().unwrap() as i32;
+
+        let mut problem = Problem::new(OptimizationDirection::Minimize);
+        let mut vars: Vec<Variable> = Vec::new();
+        for _ in 0..(self.buttons.len()) {
+            vars.push(problem.add_integer_var(1.0, (0, max)));
+        }
+
+        for (i, &joltage) in self.joltage.iter().enumerate() {
+            let mut button_constraint = LinearExpr::empty();
+            self.buttons
+                .iter()
+                .zip(&vars)
+                .filter(|(b, _)| b.toggles.contains(&i))
+                .for_each(|(_, &var)| {
+                    button_constraint.add(var, 1.0);
+                });
+            problem.add_constraint(button_constraint, ComparisonOp::Eq, joltage as f64);
+        }
+        let answer = problem.solve().ok()?;
+        Some(answer.objective().round() as usize)
+    }
+}
+
+impl Solution for Day10 {
+    type Parsed = Machines;
+    type Part1 = Result<usize, ParseError>;
+    type Part2 = Result<usize, ParseError>;
+
+    fn parse(&self, input: &str) -> Result<Machines, ParseError> {
+        let re = Regex::new(
+            r"^\[(?<indicators>[.#]*)\] (?<buttons>(\([0-9,]*\) )*)\{(?<joltage>[0-9,]*)\}",
+        )
+        .unwrap();
+        input::lines(input)
+            .map(|line| {
+                let Some(capture) = re.captures(line.text) else {
+                    return Err(line.error(line.text, "a machine like '[.#] (1) (0,1) {3,4}'"));
+                };
+                let indicators: Vec<bool> =
+                    capture["indicators"].chars().map(|c| c == '#').collect();
+                let light = |n: &str| {
+                    let i: usize = line.number(n)?;
+                    if i >= indicators.len() {
+                        let expected = format!("a light numbered below {}", indicators.len());
+                        return Err(line.error(n, expected));
+                    }
+                    Ok(i)
+                };
+                let buttons: Vec<Button> = capture["buttons"]
+                    .split_terminator(" ")
+                    .map(|b| {
+                        let toggles = b
+                            .trim_matches(['(', ')'])
+                            .split(',')
+                            .map(light)
+                            .collect::<Result<_, _>>()?;
+                        Ok(Button { toggles })
+                    })
+                    .collect::<Result<_, _>>()?;
+                let joltage: Vec<usize> = capture["joltage"]
+                    .split(',')
+                    .map(|n| line.number(n))
+                    .collect::<Result<_, _>>()?;
+                if joltage.len() != indicators.len() {
+                    let expected = format!("{} joltages, one for each light", indicators.len());
+                    return Err(line.error(&capture["joltage"], expected));
+                }
+                Ok(Machine {
+                    indicators,
+                    buttons,
+                    joltage,
+                    line: line.number,
+                    text: line.text.to_string(),
+                })
+            })
+            .collect()
+    }
+
+    fn part1(&self, machines: &Machines) -> Result<usize, ParseError> {
+        machines
+            .par_iter()
+            .map(|m| {
+                m.fewest()
+                    .ok_or_else(|| m.error("buttons that can set the lights as shown"))
+            })
+            .sum()
+    }
+
+    fn part2(&self, machines: &Machines) -> Result<usize, ParseError> {
+        machines
+            .iter()
+            .map(|m| {
+                m.fewest_for_joltage()
+                    .ok_or_else(|| m.error("buttons that can make up the joltages"))
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: [&str; 3] = [
+        "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}",
+        "[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}",
+        "[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}",
+    ];
+
+    #[test]
+    fn part1_given() {
+        println!("{}", EXAMPLE.join("\n"));
+        assert_eq!(
+            Day10.part1(&Day10.parse(&EXAMPLE.join("\n")).unwrap()),
+            Ok(7)
+        );
+    }
+
+    #[test]
+    fn part2_given() {
+        println!("{}", EXAMPLE.join("\n"));
+        assert_eq!(
+            Day10.part2(&Day10.parse(&EXAMPLE.join("\n")).unwrap()),
+            Ok(33)
+        );
+    }
+
+    #[test]
+    fn parse_error() {
+        let example = [EXAMPLE[0], "[.##.] (3) (1,x) {3,5,4,7}"];
+        println!("{}", example.join("\n"));
+        let error = Day10.parse(&example.join("\n")).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+    }
+
+    #[test]
+    fn mismatched() {
+        let error = Day10.parse("[.##.] (7) {3,5,4,7}").unwrap_err();
+        assert_eq!(
+            (error.column, error.expected.as_str()),
+            (9, "a light numbered below 4")
+        );
+        let error = Day10.parse("[.##.] (3) (1,3) {3,5}").unwrap_err();
+        assert_eq!(
+            (error.column, error.expected.as_str()),
+            (19, "4 joltages, one for each light")
+        );
+
+        // no button reaches light 0, so neither part can be done
+        let example = [EXAMPLE[0], "[#.] (1) {1,1}"];
+        let machines = Day10.parse(&example.join("\n")).unwrap();
+        let error = Day10.part1(&machines).unwrap_err();
+        assert_eq!(
+            (error.line, error.expected.as_str()),
+            (2, "buttons that can set the lights as shown")
+        );
+        assert_eq!(Day10.part2(&machines).unwrap_err().line, 2);
+    }
+
+    #[test]
+    fn answers() {
+        common::answers::verify(&Day10, env!("CARGO_MANIFEST_DIR"));
+    }
+}
@@ -0,0 +1,161 @@
+use common::Solution;
+use common::input;
+use common::parse::ParseError;
+use hashbrown::HashMap;
+use petgraph::algo::simple_paths::all_simple_paths;
+use petgraph::graph::{DiGraph, NodeIndex};
+use std::collections::hash_map::RandomState;
+
+#[derive(Default)]
+pub struct Day11;
+
+/// Every device, and the devices its outputs are wired to
+pub type Wiring = HashMap<String, Vec<String>>;
+
+fn paths<'a>(
+    map: &'a Wiring,
+    from: &'a str,
+    to: &str,
+    visited: &mut HashMap<&'a str, usize>,
+) -> usize {
+    if from == to {
+        return 1;
+    }
+    if let Some(value) = visited.get(from) {
+        return *value;
+    }
+    let mut ret = 0;
+    if let Some(references) = map.get(from) {
+        for reference in references.iter() {
+            let count = paths(map, reference, to, visited);
+            ret += count;
+        }
+    }
+    visited.insert(from, ret);
+    ret
+}
+
+impl Solution for Day11 {
+    type Parsed = Wiring;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Wiring, ParseError> {
+        let wiring: Wiring = input::lines(input)
+            .map(|line| {
+                let (node, relationships) = line
+                    .text
+                    .split_once(":")
+                    .ok_or_else(|| line.error(line.end(), "':'"))?;
+                Ok((
+                    node.to_string(),
+                    relationships
+                        .split_whitespace()
+                        .map(|s| s.to_string())
+                        .collect(),
+                ))
+            })
+            .collect::<Result<_, _>>()?;
+        // every output has to lead somewhere, either to another device or out
+        for line in input::lines(input) {
+            let (_, relationships) = line.text.split_once(':').unwrap();
+            let dangling = relationships
+                .split_whitespace()
+                .find(|dest| *dest != "out" && !wiring.contains_key(*dest));
+            if let Some(dest) = dangling {
+                return Err(line.error(dest, "a device that's listed, or 'out'"));
+            }
+        }
+        if !wiring.contains_key("you") && !wiring.contains_key("svr") {
+            return Err(ParseError::end(input, "a 'you' or 'svr' device"));
+        }
+        Ok(wiring)
+    }
+
+    fn part1(&self, map: &Wiring) -> usize {
+        // with no 'you' there's no way out from it
+        if !map.contains_key("you") {
+            return 0;
+        }
+        let mut wiring = DiGraph::<&str, ()>::new();
+        let mut nodes: HashMap<&str, NodeIndex> = map
+            .keys()
+            .map(|node| (node.as_str(), wiring.add_node(node)))
+            .collect();
+        nodes.entry("out").or_insert_with(|| wiring.add_node("out"));
+        map.iter().for_each(|(src, relationships)| {
+            for dest in relationships.iter() {
+                wiring.add_edge(nodes[src.as_str()], nodes[dest.as_str()], ());
+            }
+        });
+        all_simple_paths::<Vec<_>, _, RandomState>(&wiring, nodes["you"], nodes["out"], 0, None)
+            .count()
+    }
+
+    fn part2(&self, map: &Wiring) -> usize {
+        let mut ret = 1;
+        for (from, to) in [("svr", "fft"), ("fft", "dac"), ("dac", "out")].iter() {
+            let mut visited = HashMap::new();
+            ret *= paths(map, from, to, &mut visited);
+        }
+        ret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_given() {
+        let example = [
+            "aaa: you hhh",
+            "you: bbb ccc",
+            "bbb: ddd eee",
+            "ccc: ddd eee fff",
+            "ddd: ggg",
+            "eee: out",
+            "fff: out",
+            "ggg: out",
+            "hhh: ccc fff iii",
+            "iii: out",
+        ];
+        println!("{}", example.join("\n"));
+        assert_eq!(Day11.part1(&Day11.parse(&example.join("\n")).unwrap()), 5);
+    }
+
+    #[test]
+    fn part2_given() {
+        let example = [
+            "svr: aaa bbb",
+            "aaa: fft",
+            "fft: ccc",
+            "bbb: tty",
+            "tty: ccc",
+            "ccc: ddd eee",
+            "ddd: hub",
+            "hub: fff",
+            "eee: dac",
+            "dac: fff",
+            "fff: ggg hhh",
+            "ggg: out",
+            "hhh: out",
+        ];
+        println!("{}", example.join("\n"));
+        assert_eq!(Day11.part2(&Day11.parse(&example.join("\n")).unwrap()), 2);
+    }
+
+    #[test]
+    fn degenerate() {
+        let error = Day11.parse("you: aaa\naaa: bbb out\n").err().unwrap();
+        assert_eq!((error.line, error.column), (2, 6));
+        let error = Day11.parse("aaa: out\n").err().unwrap();
+        assert_eq!(error.expected, "a 'you' or 'svr' device");
+        assert_eq!(Day11.part1(&Day11.parse("svr: out\n").unwrap()), 0);
+    }
+
+    #[test]
+    fn answers() {
+        common::answers::verify(&Day11, env!("CARGO_MANIFEST_DIR"));
+    }
+}
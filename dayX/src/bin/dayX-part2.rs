@@ -0,0 +1,41 @@
+use clap::Parser;
+use clio::Input;
+use common::Part;
+use common::output::{Format, report};
+use dayX::DayX;
+use std::io::{self, prelude::*};
+use std::process::ExitCode;
+
+#[derive(Parser)]
+struct Opt {
+    /// Input file, use '-' for stdin
+    #[clap(value_parser, default_value = "-")]
+    input: Input,
+
+    /// how to print the answer
+    #[clap(long, value_enum, default_value_t)]
+    format: Format,
+
+    /// the number of CPU cores to use (all if unspecified)
+    #[clap(short, long, default_value_t = num_cpus::get())]
+    threads: usize,
+}
+
+fn main() -> io::Result<ExitCode> {
+    let mut opt = Opt::parse();
+
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(opt.threads)
+        .build_global()
+        .unwrap();
+
+    let mut input = String::new();
+    opt.input.read_to_string(&mut input)?;
+    Ok(report(
+        &DayX,
+        env!("CARGO_PKG_NAME"),
+        Part::Two,
+        &input,
+        opt.format,
+    ))
+}
@@ -0,0 +1,61 @@
+use common::Solution;
+use common::input;
+use common::parse::ParseError;
+
+#[derive(Default)]
+pub struct DayX;
+
+impl Solution for DayX {
+    type Parsed = Vec<String>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Vec<String>, ParseError> {
+        Ok(input::lines(input)
+            .map(|line| line.text.to_string())
+            .collect())
+    }
+
+    fn part1(&self, lines: &Vec<String>) -> usize {
+        lines.len()
+    }
+
+    fn part2(&self, lines: &Vec<String>) -> usize {
+        lines.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: [&str; 10] = [
+        "467..114..",
+        "...*......",
+        "..35..633.",
+        "......#...",
+        "617*......",
+        ".....+.58.",
+        "..592.....",
+        "......755.",
+        "...$.*....",
+        ".664.598..",
+    ];
+
+    #[test]
+    fn part1_given() {
+        println!("{}", EXAMPLE.join("\n"));
+        assert_eq!(DayX.part1(&DayX.parse(&EXAMPLE.join("\n")).unwrap()), 10);
+    }
+
+    #[test]
+    fn part2_given() {
+        println!("{}", EXAMPLE.join("\n"));
+        assert_eq!(DayX.part2(&DayX.parse(&EXAMPLE.join("\n")).unwrap()), 10);
+    }
+
+    #[test]
+    fn answers() {
+        common::answers::verify(&DayX, env!("CARGO_MANIFEST_DIR"));
+    }
+}
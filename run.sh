@@ -4,98 +4,26 @@ set -e
 
 cd "$(dirname "$(realpath "$0")")"
 
-verbose=false
-function build {
-    if $verbose; then
-        cargo build -r --bin "$1" "${cargo_args[@]}"
-    else
-        cargo build -r --bin "$1" "${cargo_args[@]}" >/dev/null 2>&1
-    fi
-}
-
-run_test=false
-cumulative=false
-desired_days=()
-desired_parts=()
-cargo_args=()
-while [[ $# -gt 0 ]]; do
-	case "$1" in
-	day*)
-		desired_days+=("$1")
-		;;
-	part*)
-		desired_parts+=("$1")
-		;;
-    test)
-        cargo fmt
-        run_test=true
-        ;;
-	scaffold)
-		shift
-		day="$1"
-		cp -r dayX "$day"
-		sed -i 's/dayX/'"$day"'/' "$day/Cargo.toml"
-		sed -i '/^\]/i \ \ \ \ \"'"$day"'\"\,' Cargo.toml
-		find "$day/src/bin/" -name '*.rs' -exec rename 'dayX' "$day" {} \;
-		exit 0
-		;;
-    -v|--verbose)
-        verbose=true
-        ;;
-    -c|--cumulative)
-        cumulative=true
-        ;;
-    *)
-        cargo_args+=("$1")
-        ;;
-	esac
-	shift
-done
-
-elapsed=0
-while read -r dir; do
-	day=$(basename "$dir")
-	if [ "${desired_days[*]}" ] && ! [[ " ${desired_days[*]} " =~ " $day " ]]; then
-		continue
-	fi
-	args=("$dir/input")
-	if [ "${desired_parts[*]}" ]; then
-		parts=("${desired_parts[@]}")
-	else
-		parts=(part{1,2})
-	fi
-	for part in "${parts[@]}"; do
-	    correct="$(cat "$dir/$part.answer" 2>/dev/null)"
-		correct="${correct:-unk}"
-		bin="target/release/$day-$part"
-		src="$dir/src/bin/$day-$part.rs"
-		cmd=("$bin" "${args[@]}")
-		if [ -f "$src" ]; then
-            if $run_test; then
-                cargo test --bin "$day-$part" "${cargo_args[@]}"
-            else
-			    build "$day-$part"
-            fi
-		fi
-		if [ -f "$bin" ] && ! $run_test; then
-			echo "$day $part:"
-			start=$(date +%s%N)
-            if $verbose; then
-                ans=$("${cmd[@]}")
-                echo "$ans"
-            else
-			    ans=$("${cmd[@]}" 2>/dev/null)
-            fi
-			end=$(date +%s%N)
-			[ "${ans:-0}" = "$correct" ] && correct=correct || correct="incorrect: $correct"
-			echo -en "\t$ans (${correct}) time: "
-            echo "$(echo $(( end - start)) 1000000 | awk '{printf "%.2f", $1/$2}')ms"
-            (( elapsed += end - start ))
-			echo
-		fi
-	done
-done < <(find . -mindepth 1 -maxdepth 1 -type d -name day\* ! -path './dayX' | sort)
-
-if $cumulative; then
-    echo "cumulative: $(echo $elapsed 1000000000 | awk '{printf "%.2f", $1/$2}')s"
-fi
+# the heavy lifting lives in the aoc crate now, this just keeps the old entrypoints working
+case "$1" in
+test)
+    shift
+    cargo fmt
+    packages=()
+    args=()
+    for arg in "$@"; do
+        if [[ "$arg" == day* ]]; then
+            packages+=(-p "$arg")
+        else
+            args+=("$arg")
+        fi
+    done
+    exec cargo test "${packages[@]:---workspace}" "${args[@]}"
+    ;;
+scaffold | bench)
+    exec cargo run -q -r -p aoc -- "$@"
+    ;;
+*)
+    exec cargo run -q -r -p aoc -- run "$@"
+    ;;
+esac